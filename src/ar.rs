@@ -0,0 +1,61 @@
+use crate::cpp;
+use crate::ffi;
+
+/// Context the asset resolver uses to resolve asset paths, e.g. a search path.
+pub struct ResolverContext {
+    pub(crate) ptr: *mut ffi::ar_ResolverContext_t,
+}
+
+impl ResolverContext {
+    pub fn new() -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::ar_ResolverContext_default(&mut ptr);
+            ResolverContext { ptr }
+        }
+    }
+
+    /// The context the configured resolver would use by default when opening `asset_path`.
+    pub fn default_for_asset(asset_path: &str) -> Self {
+        let asset_path = cpp::String::new(asset_path);
+        unsafe {
+            let mut resolver = std::ptr::null_mut();
+            ffi::ar_GetResolver(&mut resolver);
+            let mut ptr = std::ptr::null_mut();
+            ffi::ar_Resolver_CreateDefaultContextForAsset(resolver, asset_path.ptr, &mut ptr);
+            ResolverContext { ptr }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::ar_ResolverContext_IsEmpty(self.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Default for ResolverContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for ResolverContext {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::ar_ResolverContext_copy(self.ptr, &mut ptr);
+            ResolverContext { ptr }
+        }
+    }
+}
+
+impl Drop for ResolverContext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::ar_ResolverContext_dtor(self.ptr);
+        }
+    }
+}
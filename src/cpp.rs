@@ -2,10 +2,19 @@ use crate::ffi;
 use std::ffi::{CStr, CString};
 
 pub struct String {
-    ptr: *mut ffi::std_String_t,
+    pub(crate) ptr: *mut ffi::std_String_t,
 }
 
 impl String {
+    pub fn new(value: &str) -> Self {
+        unsafe {
+            let value = CString::new(value).unwrap();
+            let mut ptr = std::ptr::null_mut();
+            ffi::std_String_from_char_ptr(value.as_ptr(), &mut ptr);
+            String { ptr }
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         unsafe {
             let mut ptr = std::ptr::null();
@@ -36,4 +45,50 @@ impl Drop for String {
     }
 }
 
+/// Temporary `std::vector<std::string>` used to pass string lists across the FFI boundary.
+pub(crate) struct StringVector {
+    pub(crate) ptr: *mut ffi::std_StringVector_t,
+}
+
+impl StringVector {
+    pub(crate) fn new(values: &[&str]) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::std_StringVector_default(&mut ptr);
+            for value in values {
+                let value = String::new(value);
+                ffi::std_StringVector_push_back(ptr, value.ptr);
+            }
+            StringVector { ptr }
+        }
+    }
+
+    /// Take ownership of a vector returned from the FFI.
+    pub(crate) unsafe fn from_ptr(ptr: *mut ffi::std_StringVector_t) -> Self {
+        StringVector { ptr }
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<std::string::String> {
+        unsafe {
+            let mut size = 0;
+            ffi::std_StringVector_size(self.ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            for i in 0..size {
+                let mut ptr_str = std::ptr::null();
+                ffi::std_StringVector_op_index(self.ptr, i, &mut ptr_str);
+                let mut ptr_c_str = std::ptr::null();
+                ffi::std_String_c_str(ptr_str, &mut ptr_c_str);
+                result.push(CStr::from_ptr(ptr_c_str).to_string_lossy().to_string());
+            }
+            result
+        }
+    }
+}
 
+impl Drop for StringVector {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::std_StringVector_dtor(self.ptr);
+        }
+    }
+}
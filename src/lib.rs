@@ -1,4 +1,5 @@
 mod ffi;
+pub mod ar;
 pub mod usd;
 pub mod usd_geom;
 pub mod tf;
@@ -6,6 +7,9 @@ pub mod sdf;
 pub mod cpp;
 pub mod vt;
 
+#[cfg(test)]
+mod test_util;
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
@@ -1,14 +1,87 @@
-use crate::{ffi, tf};
+use crate::{cpp, ffi, tf, vt};
 use std::{
+    cmp::Ordering,
+    collections::HashMap,
     ffi::{c_char, CStr, CString},
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
     os::raw::c_void,
+    str::FromStr,
 };
 
+#[derive(Debug)]
+pub enum Error {
+    InvalidPath { path: String },
+    NoSpecAtPath { path: String },
+    SpecCreate { path: String },
+    LayerOpen { path: String },
+    LayerSave { identifier: String },
+    LayerExport { path: String },
+    LayerExportToString { identifier: String },
+    LayerImport { identifier: String },
+    LayerReload { identifier: String },
+    UnknownEnumValue { enum_name: &'static str, value: i64 },
+    SublayerIndex { identifier: String, index: usize, bound: usize },
+    DefaultValueSet { path: String },
+    TimeSampleSet { path: String, time: f64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidPath { path } => write!(f, "invalid path \"{path}\""),
+            Error::NoSpecAtPath { path } => write!(f, "no spec at path \"{path}\""),
+            Error::SpecCreate { path } => write!(f, "failed to create spec at \"{path}\""),
+            Error::LayerOpen { path } => write!(f, "failed to open layer \"{path}\""),
+            Error::LayerSave { identifier } => write!(f, "failed to save layer \"{identifier}\""),
+            Error::LayerExport { path } => write!(f, "failed to export layer to \"{path}\""),
+            Error::LayerExportToString { identifier } => {
+                write!(f, "failed to export layer \"{identifier}\" to a string")
+            }
+            Error::LayerImport { identifier } => {
+                write!(f, "failed to import a string into layer \"{identifier}\"")
+            }
+            Error::LayerReload { identifier } => {
+                write!(f, "failed to reload layer \"{identifier}\"")
+            }
+            Error::UnknownEnumValue { enum_name, value } => {
+                write!(f, "unknown {enum_name} value {value}")
+            }
+            Error::SublayerIndex {
+                identifier,
+                index,
+                bound,
+            } => write!(
+                f,
+                "sublayer index {index} is out of range for layer \"{identifier}\", expected less than {bound}"
+            ),
+            Error::DefaultValueSet { path } => {
+                write!(f, "failed to set the default value of \"{path}\"")
+            }
+            Error::TimeSampleSet { path, time } => {
+                write!(f, "failed to set a time sample at {time} on \"{path}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 pub struct AssetPath {
     pub(crate) ptr: *mut ffi::sdf_AssetPath_t,
 }
 
 impl AssetPath {
+    pub fn new(path: &str) -> Self {
+        let path = cpp::String::new(path);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AssetPath_new(path.ptr, &mut ptr);
+            AssetPath { ptr }
+        }
+    }
+
     pub fn asset_path(&self) -> &str {
         unsafe {
             let mut ptr = std::ptr::null();
@@ -28,11 +101,25 @@ impl AssetPath {
     }
 }
 
-pub struct AssetPathRef {
+/// A borrowed [`AssetPath`] owned by another object, such as a [`vt::Value`].
+#[repr(transparent)]
+pub struct AssetPathRef<'a> {
     pub(crate) ptr: *const ffi::sdf_AssetPath_t,
+    _marker: PhantomData<&'a AssetPath>,
+}
+
+impl<'a> AssetPathRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid object that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::sdf_AssetPath_t) -> Self {
+        AssetPathRef {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
 }
 
-impl std::ops::Deref for AssetPathRef {
+impl<'a> std::ops::Deref for AssetPathRef<'a> {
     type Target = AssetPath;
 
     fn deref(&self) -> &Self::Target {
@@ -40,6 +127,22 @@ impl std::ops::Deref for AssetPathRef {
     }
 }
 
+impl Clone for AssetPath {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AssetPath_copy(self.ptr, &mut ptr);
+            AssetPath { ptr }
+        }
+    }
+}
+
+impl fmt::Debug for AssetPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AssetPath(@{}@)", self.asset_path())
+    }
+}
+
 impl Drop for AssetPath {
     fn drop(&mut self) {
         unsafe {
@@ -66,239 +169,2628 @@ impl Layer {
         }
     }
 
-    pub fn set_default_prim(&self, name: &tf::Token) {
+    /// Return the already-open layer for `path`, or open it if it is not open yet.
+    pub fn find_or_open<P: AsRef<std::path::Path>>(
+        path: P,
+        args: &HashMap<String, String>,
+    ) -> Result<LayerRefPtr, Error> {
+        let path = path.as_ref().to_string_lossy().to_string();
+        let c_path = cpp::String::new(&path);
+        let args = FileFormatArguments::new(args);
         unsafe {
-            ffi::sdf_Layer_SetDefaultPrim(self.ptr, name.ptr);
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_FindOrOpen(c_path.ptr, args.ptr, &mut ptr);
+            let layer = LayerRefPtr { ptr };
+
+            if layer.is_invalid() {
+                Err(Error::LayerOpen { path })
+            } else {
+                Ok(layer)
+            }
         }
     }
 
-    pub fn transfer_content(&self, other: &LayerHandle) {
+    /// Return the layer for `path` if it is already open.
+    pub fn find<P: AsRef<std::path::Path>>(path: P) -> Option<LayerHandle> {
+        let path = cpp::String::new(&path.as_ref().to_string_lossy());
+        let args = FileFormatArguments::new(&HashMap::new());
         unsafe {
-            ffi::sdf_Layer_TransferContent(self.ptr, other.ptr);
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_Find(path.ptr, args.ptr, &mut ptr);
+            let layer = LayerHandle { ptr };
+
+            if layer.is_invalid() {
+                None
+            } else {
+                Some(layer)
+            }
         }
     }
-}
 
-pub struct LayerRef {
-    pub(crate) ptr: *mut ffi::sdf_Layer_t,
-}
+    /// Load the layer at `path` into a new anonymous layer that is not associated with the
+    /// file, so that saving it will not overwrite the original.
+    pub fn open_as_anonymous<P: AsRef<std::path::Path>>(path: P) -> Result<LayerRefPtr, Error> {
+        let path = path.as_ref().to_string_lossy().to_string();
+        let c_path = cpp::String::new(&path);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_OpenAsAnonymous(c_path.ptr, &mut ptr);
+            let layer = LayerRefPtr { ptr };
 
-impl LayerRef {
-    pub fn from_ptr(ptr: *mut ffi::sdf_Layer_t) -> Self {
-        Self { ptr }
+            if layer.is_invalid() {
+                Err(Error::LayerOpen { path })
+            } else {
+                Ok(layer)
+            }
+        }
     }
-}
 
-impl std::ops::Deref for LayerRef {
-    type Target = Layer;
+    /// Save the layer to its file. Unless `force` is true, a layer that is not dirty is not
+    /// written.
+    pub fn save(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_Save(self.ptr, force, &mut result);
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const LayerRef as *const Layer) }
+            if result {
+                Ok(())
+            } else {
+                Err(Error::LayerSave {
+                    identifier: self.identifier().to_string(),
+                })
+            }
+        }
     }
-}
 
-pub struct LayerHandle {
-    pub(crate) ptr: *mut ffi::sdf_LayerHandle_t,
-}
+    pub fn export<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        comment: &str,
+        args: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let path = path.as_ref().to_string_lossy().to_string();
+        let c_path = cpp::String::new(&path);
+        let comment = cpp::String::new(comment);
+        let args = FileFormatArguments::new(args);
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_Export(self.ptr, c_path.ptr, comment.ptr, args.ptr, &mut result);
 
-pub struct LayerRefPtr {
-    pub(crate) ptr: *mut ffi::sdf_LayerRefPtr_t,
-}
+            if result {
+                Ok(())
+            } else {
+                Err(Error::LayerExport { path })
+            }
+        }
+    }
 
-impl LayerRefPtr {
-    pub fn as_handle(&self) -> LayerHandle {
+    /// Serialize the layer in the usda text format.
+    pub fn export_to_string(&self) -> Result<String, Error> {
+        let string = cpp::String::new("");
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_ExportToString(self.ptr, string.ptr, &mut result);
+
+            if result {
+                Ok(string.as_str().to_string())
+            } else {
+                Err(Error::LayerExportToString {
+                    identifier: self.identifier().to_string(),
+                })
+            }
+        }
+    }
+
+    /// Replace the layer's contents with `text`, parsed as usda.
+    pub fn import_from_string(&self, text: &str) -> Result<(), Error> {
+        let text = cpp::String::new(text);
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_ImportFromString(self.ptr, text.ptr, &mut result);
+
+            if result {
+                Ok(())
+            } else {
+                Err(Error::LayerImport {
+                    identifier: self.identifier().to_string(),
+                })
+            }
+        }
+    }
+
+    /// Reload the layer from its file, discarding any unsaved edits.
+    pub fn reload(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_Reload(self.ptr, force, &mut result);
+
+            if result {
+                Ok(())
+            } else {
+                Err(Error::LayerReload {
+                    identifier: self.identifier().to_string(),
+                })
+            }
+        }
+    }
+
+    /// Remove all scene description from the layer.
+    pub fn clear(&self) {
+        unsafe {
+            ffi::sdf_Layer_Clear(self.ptr);
+        }
+    }
+
+    pub fn sublayer_paths(&self) -> Vec<String> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::sdf_LayerRefPtr_as_handle(self.ptr, &mut ptr);
-            LayerHandle { ptr }
+            ffi::sdf_Layer_GetSubLayerPaths(self.ptr, &mut ptr);
+            cpp::StringVector::from_ptr(ptr).to_vec()
         }
     }
-}
 
-impl std::ops::Deref for LayerRefPtr {
-    type Target = Layer;
+    /// Replace the list of sublayers, e.g. to reorder them. Strongest sublayer first.
+    pub fn set_sublayer_paths(&self, paths: &[&str]) {
+        let paths = cpp::StringVector::new(paths);
+        unsafe {
+            ffi::sdf_Layer_SetSubLayerPaths(self.ptr, paths.ptr);
+        }
+    }
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const LayerRefPtr as *const Layer) }
+    pub fn num_sublayer_paths(&self) -> usize {
+        unsafe {
+            let mut result = 0;
+            ffi::sdf_Layer_GetNumSubLayerPaths(self.ptr, &mut result);
+            result
+        }
     }
-}
 
-type CanReadFn = extern "C" fn(*const c_char) -> bool;
-type ReadFn = extern "C" fn(*mut ffi::sdf_Layer_t, *const c_char, bool) -> bool;
-pub use ffi::sdf_Layer_t;
-pub fn define_file_format(
-    name: &str,
-    format_id: &str,
-    version: &str,
-    target: &str,
-    extension: &str,
-    fn_can_read: CanReadFn,
-    fn_read: ReadFn,
-) {
-    let name = CString::new(name).unwrap();
-    let format_id = CString::new(format_id).unwrap();
-    let version = CString::new(version).unwrap();
-    let target = CString::new(target).unwrap();
-    let extension = CString::new(extension).unwrap();
+    /// Insert `path` into the list of sublayers at `index`. An index of `None` appends it
+    /// (weakest).
+    pub fn insert_sublayer_path(&self, path: &str, index: Option<usize>) -> Result<(), Error> {
+        let index = match index {
+            Some(index) => self._sublayer_index(index, self.num_sublayer_paths() + 1)?,
+            None => -1,
+        };
+        let path = cpp::String::new(path);
+        unsafe {
+            ffi::sdf_Layer_InsertSubLayerPath(self.ptr, path.ptr, index);
+        }
+        Ok(())
+    }
 
-    unsafe {
-        ffi::sdf_define_file_format(
-            name.as_ptr(),
-            format_id.as_ptr(),
-            version.as_ptr(),
-            target.as_ptr(),
-            extension.as_ptr(),
-            fn_can_read as *mut c_void,
-            fn_read as *mut c_void,
-        );
+    pub fn remove_sublayer_path(&self, index: usize) -> Result<(), Error> {
+        let index = self._sublayer_index(index, self.num_sublayer_paths())?;
+        unsafe {
+            ffi::sdf_Layer_RemoveSubLayerPath(self.ptr, index);
+        }
+        Ok(())
     }
-}
 
-pub struct Path {
-    pub(crate) ptr: *mut ffi::sdf_Path_t,
-}
+    pub fn sublayer_offsets(&self) -> Vec<LayerOffset> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetSubLayerOffsets(self.ptr, &mut ptr);
+            let mut size = 0;
+            ffi::sdf_LayerOffsetVector_size(ptr, &mut size);
+            let mut data = std::ptr::null();
+            ffi::sdf_LayerOffsetVector_data(ptr, &mut data);
+            let result = if size == 0 {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(data as *const LayerOffset, size).to_vec()
+            };
+            ffi::sdf_LayerOffsetVector_dtor(ptr);
+            result
+        }
+    }
 
-impl Path {
-    pub fn text(&self) -> &'static str {
+    pub fn sublayer_offset(&self, index: usize) -> Result<LayerOffset, Error> {
+        let index = self._sublayer_index(index, self.num_sublayer_paths())?;
         unsafe {
-            let mut ptr = std::ptr::null();
-            ffi::sdf_Path_GetText(self.ptr, &mut ptr);
-            CStr::from_ptr(ptr).to_str().unwrap()
+            let mut result = LayerOffset::default();
+            ffi::sdf_Layer_GetSubLayerOffset(
+                self.ptr,
+                index,
+                &mut result as *mut LayerOffset as *mut ffi::sdf_LayerOffset_t,
+            );
+            Ok(result)
         }
     }
-}
 
-impl From<&str> for Path {
-    fn from(value: &str) -> Self {
-        let cs = std::ffi::CString::new(value).unwrap();
+    pub fn set_sublayer_offset(&self, offset: &LayerOffset, index: usize) -> Result<(), Error> {
+        let index = self._sublayer_index(index, self.num_sublayer_paths())?;
+        unsafe {
+            ffi::sdf_Layer_SetSubLayerOffset(
+                self.ptr,
+                offset as *const LayerOffset as *const ffi::sdf_LayerOffset_t,
+                index,
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that `index` is less than `bound` and convert it to the `int` the C++ API takes.
+    fn _sublayer_index(&self, index: usize, bound: usize) -> Result<i32, Error> {
+        match i32::try_from(index) {
+            Ok(i) if index < bound => Ok(i),
+            _ => Err(Error::SublayerIndex {
+                identifier: self.identifier().to_string(),
+                index,
+                bound,
+            }),
+        }
+    }
 
+    pub fn default_prim(&self) -> tf::Token {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::sdf_Path_from_string(cs.as_ptr(), &mut ptr);
-            Path { ptr }
+            ffi::sdf_Layer_GetDefaultPrim(self.ptr, &mut ptr);
+            tf::Token { ptr }
         }
     }
-}
 
-pub struct PathRef {
-    pub(crate) ptr: *const ffi::sdf_Path_t,
-}
+    pub fn set_default_prim(&self, name: &tf::Token) {
+        unsafe {
+            ffi::sdf_Layer_SetDefaultPrim(self.ptr, name.ptr);
+        }
+    }
 
-impl std::ops::Deref for PathRef {
-    type Target = Path;
+    pub fn has_default_prim(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_HasDefaultPrim(self.ptr, &mut result);
+            result
+        }
+    }
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const PathRef as *const Path) }
+    pub fn clear_default_prim(&self) {
+        unsafe {
+            ffi::sdf_Layer_ClearDefaultPrim(self.ptr);
+        }
     }
-}
 
-impl Drop for Path {
-    fn drop(&mut self) {
+    pub fn start_time_code(&self) -> f64 {
         unsafe {
-            ffi::sdf_Path_dtor(self.ptr);
+            let mut result = 0.0;
+            ffi::sdf_Layer_GetStartTimeCode(self.ptr, &mut result);
+            result
         }
     }
-}
 
-pub struct PathVector {
-    pub(crate) ptr: *mut ffi::sdf_PathVector_t,
-}
+    pub fn set_start_time_code(&self, value: f64) {
+        unsafe {
+            ffi::sdf_Layer_SetStartTimeCode(self.ptr, value);
+        }
+    }
 
-impl PathVector {
-    pub fn size(&self) -> usize {
+    pub fn has_start_time_code(&self) -> bool {
         unsafe {
-            let mut size = 0;
-            ffi::sdf_PathVector_size(self.ptr, &mut size);
-            size
+            let mut result = false;
+            ffi::sdf_Layer_HasStartTimeCode(self.ptr, &mut result);
+            result
         }
     }
 
-    pub fn at(&self, index: usize) -> PathRef {
+    pub fn clear_start_time_code(&self) {
         unsafe {
-            let mut ptr = std::ptr::null();
-            ffi::sdf_PathVector_op_index(self.ptr, index, &mut ptr);
-            PathRef { ptr }
+            ffi::sdf_Layer_ClearStartTimeCode(self.ptr);
         }
     }
 
-    pub fn iter(&self) -> PathVectorIterator {
-        PathVectorIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
+    pub fn end_time_code(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::sdf_Layer_GetEndTimeCode(self.ptr, &mut result);
+            result
         }
     }
-}
 
-impl Drop for PathVector {
-    fn drop(&mut self) {
+    pub fn set_end_time_code(&self, value: f64) {
         unsafe {
-            ffi::sdf_PathVector_dtor(self.ptr);
+            ffi::sdf_Layer_SetEndTimeCode(self.ptr, value);
         }
     }
-}
 
-impl Default for PathVector {
-    fn default() -> Self {
+    pub fn has_end_time_code(&self) -> bool {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::sdf_PathVector_default(&mut ptr);
-            PathVector { ptr }
+            let mut result = false;
+            ffi::sdf_Layer_HasEndTimeCode(self.ptr, &mut result);
+            result
         }
     }
-}
 
-pub struct PathVectorIterator<'a> {
-    vec: &'a PathVector,
-    current: usize,
-    end: usize,
-}
+    pub fn clear_end_time_code(&self) {
+        unsafe {
+            ffi::sdf_Layer_ClearEndTimeCode(self.ptr);
+        }
+    }
 
-impl<'a> Iterator for PathVectorIterator<'a> {
-    type Item = PathRef;
+    pub fn time_codes_per_second(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::sdf_Layer_GetTimeCodesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
+    pub fn set_time_codes_per_second(&self, value: f64) {
+        unsafe {
+            ffi::sdf_Layer_SetTimeCodesPerSecond(self.ptr, value);
         }
     }
-}
 
-pub struct ValueTypeName {
-    pub(crate) ptr: *mut ffi::sdf_ValueTypeName_t,
-}
+    pub fn has_time_codes_per_second(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_HasTimeCodesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
 
-impl ValueTypeName {
-    pub fn as_token(&self) -> tf::Token {
+    pub fn clear_time_codes_per_second(&self) {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::sdf_ValueTypeName_GetAsToken(self.ptr, &mut ptr);
-            tf::Token { ptr }
+            ffi::sdf_Layer_ClearTimeCodesPerSecond(self.ptr);
+        }
+    }
+
+    pub fn frames_per_second(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::sdf_Layer_GetFramesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn set_frames_per_second(&self, value: f64) {
+        unsafe {
+            ffi::sdf_Layer_SetFramesPerSecond(self.ptr, value);
         }
     }
 
-    pub fn role(&self) -> tf::TokenRef {
+    pub fn has_frames_per_second(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_HasFramesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn clear_frames_per_second(&self) {
+        unsafe {
+            ffi::sdf_Layer_ClearFramesPerSecond(self.ptr);
+        }
+    }
+
+    pub fn documentation(&self) -> &str {
         unsafe {
             let mut ptr = std::ptr::null();
-            ffi::sdf_ValueTypeName_GetRole(self.ptr, &mut ptr);
-            tf::TokenRef { ptr }
+            ffi::sdf_Layer_GetDocumentation(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
         }
     }
-}
 
-impl Drop for ValueTypeName {
-    fn drop(&mut self) {
+    pub fn set_documentation(&self, value: &str) {
+        let value = cpp::String::new(value);
         unsafe {
-            ffi::sdf_ValueTypeName_dtor(self.ptr);
+            ffi::sdf_Layer_SetDocumentation(self.ptr, value.ptr);
         }
     }
-}
 
-pub enum Variability {
-    Varying,
+    pub fn comment(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Layer_GetComment(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn set_comment(&self, value: &str) {
+        let value = cpp::String::new(value);
+        unsafe {
+            ffi::sdf_Layer_SetComment(self.ptr, value.ptr);
+        }
+    }
+
+    pub fn custom_layer_data(&self) -> vt::Dictionary {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetCustomLayerData(self.ptr, &mut ptr);
+            vt::Dictionary { ptr }
+        }
+    }
+
+    pub fn set_custom_layer_data(&self, value: &vt::Dictionary) {
+        unsafe {
+            ffi::sdf_Layer_SetCustomLayerData(self.ptr, value.ptr);
+        }
+    }
+
+    pub fn has_custom_layer_data(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_HasCustomLayerData(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn clear_custom_layer_data(&self) {
+        unsafe {
+            ffi::sdf_Layer_ClearCustomLayerData(self.ptr);
+        }
+    }
+
+    pub fn transfer_content(&self, other: &LayerHandle) {
+        unsafe {
+            ffi::sdf_Layer_TransferContent(self.ptr, other.ptr);
+        }
+    }
+
+    pub fn identifier(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Layer_GetIdentifier(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_IsDirty(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Layer_IsAnonymous(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn pseudo_root(&self) -> PrimSpec {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetPseudoRoot(self.ptr, &mut ptr);
+            PrimSpec { ptr }
+        }
+    }
+
+    pub fn root_prims(&self) -> Vec<PrimSpec> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetRootPrims(self.ptr, &mut ptr);
+            prim_spec_vector_to_vec(ptr)
+        }
+    }
+
+    pub fn get_prim_at_path(&self, path: &Path) -> Result<PrimSpec, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetPrimAtPath(self.ptr, path.ptr, &mut ptr);
+            let spec = PrimSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::NoSpecAtPath {
+                    path: path.text().to_string(),
+                })
+            }
+        }
+    }
+
+    pub fn get_attribute_at_path(&self, path: &Path) -> Result<AttributeSpec, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetAttributeAtPath(self.ptr, path.ptr, &mut ptr);
+            let spec = AttributeSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::NoSpecAtPath {
+                    path: path.text().to_string(),
+                })
+            }
+        }
+    }
+
+    pub fn get_relationship_at_path(&self, path: &Path) -> Result<RelationshipSpec, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetRelationshipAtPath(self.ptr, path.ptr, &mut ptr);
+            let spec = RelationshipSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::NoSpecAtPath {
+                    path: path.text().to_string(),
+                })
+            }
+        }
+    }
+
+    pub fn list_time_samples_for_path(&self, path: &Path) -> Vec<f64> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_ListTimeSamplesForPath(self.ptr, path.ptr, &mut ptr);
+            let mut size = 0;
+            ffi::std_DoubleSet_size(ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            let mut it = std::ptr::null_mut();
+            ffi::std_DoubleSet_begin(ptr, &mut it);
+            for _ in 0..size {
+                let mut value = std::ptr::null();
+                ffi::std_DoubleSetIterator_op_deref(it, &mut value);
+                result.push(*value);
+                let mut dummy = std::ptr::null_mut();
+                ffi::std_DoubleSetIterator_op_inc(it, &mut dummy);
+            }
+            ffi::std_DoubleSetIterator_dtor(it);
+            ffi::std_DoubleSet_dtor(ptr);
+            result
+        }
+    }
+
+    pub fn num_time_samples_for_path(&self, path: &Path) -> usize {
+        unsafe {
+            let mut result = 0;
+            ffi::sdf_Layer_GetNumTimeSamplesForPath(self.ptr, path.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn query_time_sample(&self, path: &Path, time: f64) -> Option<vt::Value> {
+        unsafe {
+            let value = vt::Value::new();
+            let mut result = false;
+            ffi::sdf_Layer_QueryTimeSample(self.ptr, path.ptr, time, value.ptr, &mut result);
+            if result {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Author a time sample for the attribute at `path`.
+    ///
+    /// SdfLayer::SetTimeSample() does not report failure, so we treat a missing sample at
+    /// `time` afterwards as an error, e.g. when there is no attribute spec at `path` or the
+    /// value cannot be cast to the attribute's type.
+    pub fn set_time_sample(&self, path: &Path, time: f64, value: &vt::Value) -> Result<(), Error> {
+        unsafe {
+            ffi::sdf_Layer_SetTimeSample(self.ptr, path.ptr, time, value.ptr);
+        }
+
+        if self.query_time_sample(path, time).is_some() {
+            Ok(())
+        } else {
+            Err(Error::TimeSampleSet {
+                path: path.text().to_string(),
+                time,
+            })
+        }
+    }
+
+    pub fn erase_time_sample(&self, path: &Path, time: f64) {
+        unsafe {
+            ffi::sdf_Layer_EraseTimeSample(self.ptr, path.ptr, time);
+        }
+    }
+}
+
+pub struct LayerRef {
+    pub(crate) ptr: *mut ffi::sdf_Layer_t,
+}
+
+impl LayerRef {
+    pub fn from_ptr(ptr: *mut ffi::sdf_Layer_t) -> Self {
+        Self { ptr }
+    }
+}
+
+impl std::ops::Deref for LayerRef {
+    type Target = Layer;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const LayerRef as *const Layer) }
+    }
+}
+
+/// A time offset and scale applied when a layer is referenced or sublayered: a time `t` in
+/// the referenced layer maps to `t * scale + offset` in the referencing layer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerOffset {
+    pub offset: f64,
+    pub scale: f64,
+}
+
+impl LayerOffset {
+    pub fn new(offset: f64, scale: f64) -> Self {
+        LayerOffset { offset, scale }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.offset == 0.0 && self.scale == 1.0
+    }
+
+    /// Map `time` from the referenced layer into the referencing layer.
+    pub fn apply(&self, time: f64) -> f64 {
+        time * self.scale + self.offset
+    }
+
+    /// The offset that maps times back from the referencing layer into the referenced layer.
+    /// A scale of zero inverts to an infinite scale, as with `SdfLayerOffset::GetInverse`.
+    pub fn inverse(&self) -> LayerOffset {
+        if self.is_identity() {
+            return *self;
+        }
+
+        let scale = if self.scale != 0.0 {
+            1.0 / self.scale
+        } else {
+            f64::INFINITY
+        };
+
+        LayerOffset {
+            offset: -self.offset * scale,
+            scale,
+        }
+    }
+}
+
+/// Compose two offsets so that `(a * b).apply(t) == a.apply(b.apply(t))`, as when a layer
+/// offset by `b` is itself referenced with offset `a`.
+impl std::ops::Mul for LayerOffset {
+    type Output = LayerOffset;
+
+    fn mul(self, rhs: LayerOffset) -> Self::Output {
+        LayerOffset {
+            offset: self.scale * rhs.offset + self.offset,
+            scale: self.scale * rhs.scale,
+        }
+    }
+}
+
+impl Default for LayerOffset {
+    fn default() -> Self {
+        LayerOffset {
+            offset: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+pub struct LayerHandle {
+    pub(crate) ptr: *mut ffi::sdf_LayerHandle_t,
+}
+
+impl std::ops::Deref for LayerHandle {
+    type Target = Layer;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const LayerHandle as *const Layer) }
+    }
+}
+
+impl LayerHandle {
+    pub fn is_invalid(&self) -> bool {
+        unsafe {
+            let mut result = true;
+            ffi::sdf_LayerHandle_is_invalid(self.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Drop for LayerHandle {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_LayerHandle_dtor(self.ptr);
+        }
+    }
+}
+
+pub struct LayerRefPtr {
+    pub(crate) ptr: *mut ffi::sdf_LayerRefPtr_t,
+}
+
+impl LayerRefPtr {
+    pub fn is_invalid(&self) -> bool {
+        unsafe {
+            let mut result = true;
+            ffi::sdf_LayerRefPtr_is_invalid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn as_handle(&self) -> LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_LayerRefPtr_as_handle(self.ptr, &mut ptr);
+            LayerHandle { ptr }
+        }
+    }
+}
+
+impl std::ops::Deref for LayerRefPtr {
+    type Target = Layer;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const LayerRefPtr as *const Layer) }
+    }
+}
+
+impl Drop for LayerRefPtr {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_LayerRefPtr_dtor(self.ptr);
+        }
+    }
+}
+
+pub(crate) unsafe fn layer_handle_vector_to_vec(
+    ptr: *mut ffi::sdf_LayerHandleVector_t,
+) -> Vec<LayerHandle> {
+    let mut size = 0;
+    ffi::sdf_LayerHandleVector_size(ptr, &mut size);
+    let mut result = Vec::with_capacity(size);
+    for i in 0..size {
+        let mut ptr_elem = std::ptr::null();
+        ffi::sdf_LayerHandleVector_op_index(ptr, i, &mut ptr_elem);
+        let mut ptr_copy = std::ptr::null_mut();
+        ffi::sdf_LayerHandle_copy(ptr_elem, &mut ptr_copy);
+        result.push(LayerHandle { ptr: ptr_copy });
+    }
+    ffi::sdf_LayerHandleVector_dtor(ptr);
+    result
+}
+
+/// Temporary `SdfLayer::FileFormatArguments` built from a Rust map.
+struct FileFormatArguments {
+    ptr: *mut ffi::sdf_FileFormatArguments_t,
+}
+
+impl FileFormatArguments {
+    fn new(args: &HashMap<String, String>) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_FileFormatArguments_default(&mut ptr);
+            for (key, value) in args {
+                let key = cpp::String::new(key);
+                let value = cpp::String::new(value);
+                ffi::sdf_FileFormatArguments_insert(ptr, key.ptr, value.ptr);
+            }
+            FileFormatArguments { ptr }
+        }
+    }
+}
+
+impl Drop for FileFormatArguments {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_FileFormatArguments_dtor(self.ptr);
+        }
+    }
+}
+
+type CanReadFn = extern "C" fn(*const c_char) -> bool;
+type ReadFn = extern "C" fn(*mut ffi::sdf_Layer_t, *const c_char, bool) -> bool;
+pub use ffi::sdf_Layer_t;
+pub fn define_file_format(
+    name: &str,
+    format_id: &str,
+    version: &str,
+    target: &str,
+    extension: &str,
+    fn_can_read: CanReadFn,
+    fn_read: ReadFn,
+) {
+    let name = CString::new(name).unwrap();
+    let format_id = CString::new(format_id).unwrap();
+    let version = CString::new(version).unwrap();
+    let target = CString::new(target).unwrap();
+    let extension = CString::new(extension).unwrap();
+
+    unsafe {
+        ffi::sdf_define_file_format(
+            name.as_ptr(),
+            format_id.as_ptr(),
+            version.as_ptr(),
+            target.as_ptr(),
+            extension.as_ptr(),
+            fn_can_read as *mut c_void,
+            fn_read as *mut c_void,
+        );
+    }
+}
+
+pub struct Path {
+    pub(crate) ptr: *mut ffi::sdf_Path_t,
+}
+
+impl Path {
+    /// Parse `text` as a path, returning an error if it is not a valid path string.
+    pub fn new(text: &str) -> Result<Path, Error> {
+        let cs = CString::new(text).map_err(|_| Error::InvalidPath {
+            path: text.to_string(),
+        })?;
+
+        let path = unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_from_string(cs.as_ptr(), &mut ptr);
+            Path { ptr }
+        };
+
+        if path.is_empty() {
+            Err(Error::InvalidPath {
+                path: text.to_string(),
+            })
+        } else {
+            Ok(path)
+        }
+    }
+
+    pub fn empty() -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_default(&mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn absolute_root() -> Path {
+        Path::new("/").unwrap()
+    }
+
+    pub fn is_valid_identifier(name: &str) -> bool {
+        let name = cpp::String::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsValidIdentifier(name.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Path_GetText(self.ptr, &mut ptr);
+            CStr::from_ptr(ptr).to_str().unwrap()
+        }
+    }
+
+    /// The last element of the path, e.g. the prim name or property name.
+    pub fn name(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Path_GetName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn name_token(&self) -> tf::TokenRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Path_GetNameToken(self.ptr, &mut ptr);
+            tf::TokenRef::from_ptr(ptr)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsEmpty(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsAbsolutePath(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_absolute_root(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsAbsoluteRootPath(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_prim_path(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsPrimPath(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_property_path(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_IsPropertyPath(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn parent_path(&self) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_GetParentPath(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    /// The prim path of this path, stripping any property or variant selection components.
+    pub fn prim_path(&self) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_GetPrimPath(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn append_child(&self, name: &str) -> Result<Path, Error> {
+        let token = tf::Token::new(name);
+        let path = unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_AppendChild(self.ptr, token.ptr, &mut ptr);
+            Path { ptr }
+        };
+
+        if path.is_empty() {
+            Err(Error::InvalidPath {
+                path: format!("{}/{}", self.text(), name),
+            })
+        } else {
+            Ok(path)
+        }
+    }
+
+    pub fn append_property(&self, name: &str) -> Result<Path, Error> {
+        let token = tf::Token::new(name);
+        let path = unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_AppendProperty(self.ptr, token.ptr, &mut ptr);
+            Path { ptr }
+        };
+
+        if path.is_empty() {
+            Err(Error::InvalidPath {
+                path: format!("{}.{}", self.text(), name),
+            })
+        } else {
+            Ok(path)
+        }
+    }
+
+    pub fn append_variant_selection(&self, variant_set: &str, variant: &str) -> Result<Path, Error> {
+        let variant_set_str = cpp::String::new(variant_set);
+        let variant_str = cpp::String::new(variant);
+        let path = unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_AppendVariantSelection(
+                self.ptr,
+                variant_set_str.ptr,
+                variant_str.ptr,
+                &mut ptr,
+            );
+            Path { ptr }
+        };
+
+        if path.is_empty() {
+            Err(Error::InvalidPath {
+                path: format!("{}{{{}={}}}", self.text(), variant_set, variant),
+            })
+        } else {
+            Ok(path)
+        }
+    }
+
+    pub fn has_prefix(&self, prefix: &Path) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_HasPrefix(self.ptr, prefix.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Replace `old_prefix` with `new_prefix` in this path, including in any target paths.
+    /// Returns this path unchanged if it does not have `old_prefix`.
+    pub fn replace_prefix(&self, old_prefix: &Path, new_prefix: &Path) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_ReplacePrefix(self.ptr, old_prefix.ptr, new_prefix.ptr, true, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn common_prefix(&self, other: &Path) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_GetCommonPrefix(self.ptr, other.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+}
+
+impl TryFrom<&str> for Path {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Path::new(value)
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Path::new(s)
+    }
+}
+
+impl Clone for Path {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Path_copy(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+}
+
+impl PartialEq for Path {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Path_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Eq for Path {}
+
+impl Hash for Path {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe {
+            let mut result = 0;
+            ffi::sdf_Path_GetHash(self.ptr, &mut result);
+            state.write_usize(result);
+        }
+    }
+}
+
+impl PartialOrd for Path {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Path {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            unsafe {
+                let mut less = false;
+                ffi::sdf_Path_op_lt(self.ptr, other.ptr, &mut less);
+                if less {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Path({:?})", self.text())
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+
+/// A borrowed [`Path`] owned by another object, such as a [`PathVector`].
+#[repr(transparent)]
+pub struct PathRef<'a> {
+    pub(crate) ptr: *const ffi::sdf_Path_t,
+    _marker: PhantomData<&'a Path>,
+}
+
+impl<'a> PathRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid path that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::sdf_Path_t) -> Self {
+        PathRef {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a> std::ops::Deref for PathRef<'a> {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const PathRef as *const Path) }
+    }
+}
+
+impl Drop for Path {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_Path_dtor(self.ptr);
+        }
+    }
+}
+
+pub struct PathVector {
+    pub(crate) ptr: *mut ffi::sdf_PathVector_t,
+}
+
+impl PathVector {
+    pub fn size(&self) -> usize {
+        unsafe {
+            let mut size = 0;
+            ffi::sdf_PathVector_size(self.ptr, &mut size);
+            size
+        }
+    }
+
+    pub fn at(&self, index: usize) -> PathRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_PathVector_op_index(self.ptr, index, &mut ptr);
+            PathRef::from_ptr(ptr)
+        }
+    }
+
+    pub fn push(&mut self, path: &Path) {
+        unsafe {
+            ffi::sdf_PathVector_push_back(self.ptr, path.ptr);
+        }
+    }
+
+    pub fn iter(&self) -> PathVectorIterator {
+        PathVectorIterator {
+            vec: self,
+            current: 0,
+            end: self.size(),
+        }
+    }
+}
+
+impl Drop for PathVector {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_PathVector_dtor(self.ptr);
+        }
+    }
+}
+
+impl Default for PathVector {
+    fn default() -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathVector_default(&mut ptr);
+            PathVector { ptr }
+        }
+    }
+}
+
+impl FromIterator<Path> for PathVector {
+    fn from_iter<I: IntoIterator<Item = Path>>(iter: I) -> Self {
+        let mut result = PathVector::default();
+        for path in iter {
+            result.push(&path);
+        }
+        result
+    }
+}
+
+impl<'a> IntoIterator for &'a PathVector {
+    type Item = PathRef<'a>;
+    type IntoIter = PathVectorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct PathVectorIterator<'a> {
+    vec: &'a PathVector,
+    current: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for PathVectorIterator<'a> {
+    type Item = PathRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current == self.end {
+            None
+        } else {
+            let cur = self.current;
+            self.current += 1;
+            Some(self.vec.at(cur))
+        }
+    }
+}
+
+/// Temporary `SdfPathSet` used to pass paths across the FFI.
+pub(crate) struct PathSet {
+    pub(crate) ptr: *mut ffi::sdf_PathSet_t,
+}
+
+impl PathSet {
+    pub(crate) fn new(paths: &[Path]) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathSet_default(&mut ptr);
+            for path in paths {
+                ffi::sdf_PathSet_insert(ptr, path.ptr);
+            }
+            PathSet { ptr }
+        }
+    }
+
+    /// Take ownership of a set returned from the FFI.
+    pub(crate) unsafe fn from_ptr(ptr: *mut ffi::sdf_PathSet_t) -> Self {
+        PathSet { ptr }
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<Path> {
+        unsafe {
+            let mut size = 0;
+            ffi::sdf_PathSet_size(self.ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            let mut it = std::ptr::null_mut();
+            ffi::sdf_PathSet_begin(self.ptr, &mut it);
+            for _ in 0..size {
+                let mut path = std::ptr::null();
+                ffi::sdf_PathSetIterator_op_deref(it, &mut path);
+                let mut ptr = std::ptr::null_mut();
+                ffi::sdf_Path_copy(path, &mut ptr);
+                result.push(Path { ptr });
+                let mut dummy = std::ptr::null_mut();
+                ffi::sdf_PathSetIterator_op_inc(it, &mut dummy);
+            }
+            ffi::sdf_PathSetIterator_dtor(it);
+            result
+        }
+    }
+}
+
+impl Drop for PathSet {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_PathSet_dtor(self.ptr);
+        }
+    }
+}
+
+pub struct ValueTypeName {
+    pub(crate) ptr: *mut ffi::sdf_ValueTypeName_t,
+}
+
+impl ValueTypeName {
+    /// Look up a registered value type by its name in scene description, e.g. `"float3[]"`.
+    pub fn find(name: &str) -> Option<ValueTypeName> {
+        let name = cpp::String::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Schema_FindType(name.ptr, &mut ptr);
+            let mut is_valid = false;
+            ffi::sdf_ValueTypeName_is_valid(ptr, &mut is_valid);
+            if is_valid {
+                Some(ValueTypeName { ptr })
+            } else {
+                ffi::sdf_ValueTypeName_dtor(ptr);
+                None
+            }
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_ValueTypeName_is_valid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// The C++ value type, without the role, so `color3f` and `float3` are both `GfVec3f`.
+    pub fn tf_type(&self) -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_ValueTypeName_GetType(self.ptr, &mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    /// Whether values of this type are held as a `T`, ignoring roles.
+    pub(crate) fn holds_type<T: vt::ValueMember>(&self) -> bool {
+        self.tf_type() == T::tf_type()
+    }
+
+    pub fn as_token(&self) -> tf::Token {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_ValueTypeName_GetAsToken(self.ptr, &mut ptr);
+            tf::Token { ptr }
+        }
+    }
+
+    pub fn role(&self) -> tf::TokenRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_ValueTypeName_GetRole(self.ptr, &mut ptr);
+            tf::TokenRef::from_ptr(ptr)
+        }
+    }
+}
+
+impl Drop for ValueTypeName {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_ValueTypeName_dtor(self.ptr);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variability {
+    Varying,
     Uniform,
 }
+
+impl From<Variability> for ffi::sdf_Variability {
+    fn from(value: Variability) -> Self {
+        match value {
+            Variability::Varying => ffi::sdf_Variability::sdf_Variability_SdfVariabilityVarying,
+            Variability::Uniform => ffi::sdf_Variability::sdf_Variability_SdfVariabilityUniform,
+        }
+    }
+}
+
+/// A time value in layer time, as stored in `timecode`-typed attributes.
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TimeCode(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Specifier {
+    Def,
+    Over,
+    Class,
+}
+
+impl From<Specifier> for ffi::sdf_Specifier {
+    fn from(value: Specifier) -> Self {
+        match value {
+            Specifier::Def => ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierDef,
+            Specifier::Over => ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierOver,
+            Specifier::Class => ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierClass,
+        }
+    }
+}
+
+impl TryFrom<ffi::sdf_Specifier> for Specifier {
+    type Error = Error;
+
+    fn try_from(value: ffi::sdf_Specifier) -> Result<Self, Self::Error> {
+        match value {
+            ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierDef => Ok(Specifier::Def),
+            ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierOver => Ok(Specifier::Over),
+            ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierClass => Ok(Specifier::Class),
+            _ => Err(Error::UnknownEnumValue {
+                enum_name: "SdfSpecifier",
+                value: value as i64,
+            }),
+        }
+    }
+}
+
+impl TryFrom<ffi::sdf_Variability> for Variability {
+    type Error = Error;
+
+    fn try_from(value: ffi::sdf_Variability) -> Result<Self, Self::Error> {
+        match value {
+            ffi::sdf_Variability::sdf_Variability_SdfVariabilityVarying => Ok(Variability::Varying),
+            ffi::sdf_Variability::sdf_Variability_SdfVariabilityUniform => Ok(Variability::Uniform),
+            _ => Err(Error::UnknownEnumValue {
+                enum_name: "SdfVariability",
+                value: value as i64,
+            }),
+        }
+    }
+}
+
+/// A prim in a layer, independent of any composed stage.
+pub struct PrimSpec {
+    pub(crate) ptr: *mut ffi::sdf_PrimSpecHandle_t,
+}
+
+impl PrimSpec {
+    /// Create a root prim `name` in `layer`.
+    pub fn new(
+        layer: &LayerHandle,
+        name: &str,
+        specifier: Specifier,
+        type_name: &str,
+    ) -> Result<PrimSpec, Error> {
+        let name_str = cpp::String::new(name);
+        let type_name = cpp::String::new(type_name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpec_New_in_layer(
+                layer.ptr,
+                name_str.ptr,
+                specifier.into(),
+                type_name.ptr,
+                &mut ptr,
+            );
+            let spec = PrimSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::SpecCreate {
+                    path: format!("/{}", name),
+                })
+            }
+        }
+    }
+
+    /// Create a prim `name` as a child of `parent`.
+    pub fn new_child(
+        parent: &PrimSpec,
+        name: &str,
+        specifier: Specifier,
+        type_name: &str,
+    ) -> Result<PrimSpec, Error> {
+        let name_str = cpp::String::new(name);
+        let type_name = cpp::String::new(type_name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpec_New_in_prim(
+                parent.ptr,
+                name_str.ptr,
+                specifier.into(),
+                type_name.ptr,
+                &mut ptr,
+            );
+            let spec = PrimSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::SpecCreate {
+                    path: format!("{}/{}", parent.path().text(), name),
+                })
+            }
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_PrimSpecHandle_is_valid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn path(&self) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetPath(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn layer(&self) -> LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetLayer(self.ptr, &mut ptr);
+            LayerHandle { ptr }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_PrimSpecHandle_GetName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn type_name(&self) -> tf::Token {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetTypeName(self.ptr, &mut ptr);
+            tf::Token { ptr }
+        }
+    }
+
+    pub fn set_type_name(&self, type_name: &str) {
+        let type_name = cpp::String::new(type_name);
+        unsafe {
+            ffi::sdf_PrimSpecHandle_SetTypeName(self.ptr, type_name.ptr);
+        }
+    }
+
+    pub fn specifier(&self) -> Result<Specifier, Error> {
+        unsafe {
+            let mut result = ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierDef;
+            ffi::sdf_PrimSpecHandle_GetSpecifier(self.ptr, &mut result);
+            result.try_into()
+        }
+    }
+
+    pub fn set_specifier(&self, specifier: Specifier) {
+        unsafe {
+            ffi::sdf_PrimSpecHandle_SetSpecifier(self.ptr, specifier.into());
+        }
+    }
+
+    pub fn name_children(&self) -> Vec<PrimSpec> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetNameChildren(self.ptr, &mut ptr);
+            prim_spec_vector_to_vec(ptr)
+        }
+    }
+
+    pub fn attributes(&self) -> Vec<AttributeSpec> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetAttributes(self.ptr, &mut ptr);
+            let mut size = 0;
+            ffi::sdf_AttributeSpecHandleVector_size(ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            for i in 0..size {
+                let mut ptr_elem = std::ptr::null();
+                ffi::sdf_AttributeSpecHandleVector_op_index(ptr, i, &mut ptr_elem);
+                let mut ptr_copy = std::ptr::null_mut();
+                ffi::sdf_AttributeSpecHandle_copy(ptr_elem, &mut ptr_copy);
+                result.push(AttributeSpec { ptr: ptr_copy });
+            }
+            ffi::sdf_AttributeSpecHandleVector_dtor(ptr);
+            result
+        }
+    }
+
+    pub fn relationships(&self) -> Vec<RelationshipSpec> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PrimSpecHandle_GetRelationships(self.ptr, &mut ptr);
+            let mut size = 0;
+            ffi::sdf_RelationshipSpecHandleVector_size(ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            for i in 0..size {
+                let mut ptr_elem = std::ptr::null();
+                ffi::sdf_RelationshipSpecHandleVector_op_index(ptr, i, &mut ptr_elem);
+                let mut ptr_copy = std::ptr::null_mut();
+                ffi::sdf_RelationshipSpecHandle_copy(ptr_elem, &mut ptr_copy);
+                result.push(RelationshipSpec { ptr: ptr_copy });
+            }
+            ffi::sdf_RelationshipSpecHandleVector_dtor(ptr);
+            result
+        }
+    }
+
+    pub fn remove_property(&self, name: &str) -> Result<(), Error> {
+        let path = self.path().append_property(name)?;
+
+        let layer = self.layer();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Layer_GetPropertyAtPath((*layer).ptr, path.ptr, &mut ptr);
+            let mut is_valid = false;
+            ffi::sdf_PropertySpecHandle_is_valid(ptr, &mut is_valid);
+            if is_valid {
+                ffi::sdf_PrimSpecHandle_RemoveProperty(self.ptr, ptr);
+            }
+            ffi::sdf_PropertySpecHandle_dtor(ptr);
+
+            if is_valid {
+                Ok(())
+            } else {
+                Err(Error::NoSpecAtPath {
+                    path: path.text().to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl Drop for PrimSpec {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_PrimSpecHandle_dtor(self.ptr);
+        }
+    }
+}
+
+unsafe fn prim_spec_vector_to_vec(ptr: *mut ffi::sdf_PrimSpecHandleVector_t) -> Vec<PrimSpec> {
+    let mut size = 0;
+    ffi::sdf_PrimSpecHandleVector_size(ptr, &mut size);
+    let mut result = Vec::with_capacity(size);
+    for i in 0..size {
+        let mut ptr_elem = std::ptr::null();
+        ffi::sdf_PrimSpecHandleVector_op_index(ptr, i, &mut ptr_elem);
+        let mut ptr_copy = std::ptr::null_mut();
+        ffi::sdf_PrimSpecHandle_copy(ptr_elem, &mut ptr_copy);
+        result.push(PrimSpec { ptr: ptr_copy });
+    }
+    ffi::sdf_PrimSpecHandleVector_dtor(ptr);
+    result
+}
+
+pub struct AttributeSpec {
+    pub(crate) ptr: *mut ffi::sdf_AttributeSpecHandle_t,
+}
+
+impl AttributeSpec {
+    pub fn new(
+        owner: &PrimSpec,
+        name: &str,
+        type_name: &ValueTypeName,
+        variability: Variability,
+        custom: bool,
+    ) -> Result<AttributeSpec, Error> {
+        let name_str = cpp::String::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AttributeSpec_New(
+                owner.ptr,
+                name_str.ptr,
+                type_name.ptr,
+                variability.into(),
+                custom,
+                &mut ptr,
+            );
+            let spec = AttributeSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::SpecCreate {
+                    path: format!("{}.{}", owner.path().text(), name),
+                })
+            }
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_AttributeSpecHandle_is_valid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn path(&self) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AttributeSpecHandle_GetPath(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn layer(&self) -> LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AttributeSpecHandle_GetLayer(self.ptr, &mut ptr);
+            LayerHandle { ptr }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_AttributeSpecHandle_GetName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn type_name(&self) -> ValueTypeName {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AttributeSpecHandle_GetTypeName(self.ptr, &mut ptr);
+            ValueTypeName { ptr }
+        }
+    }
+
+    pub fn variability(&self) -> Result<Variability, Error> {
+        unsafe {
+            let mut result = ffi::sdf_Variability::sdf_Variability_SdfVariabilityVarying;
+            ffi::sdf_AttributeSpecHandle_GetVariability(self.ptr, &mut result);
+            result.try_into()
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_AttributeSpecHandle_IsCustom(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn has_default_value(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_AttributeSpecHandle_HasDefaultValue(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn default_value(&self) -> Option<vt::Value> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AttributeSpecHandle_GetDefaultValue(self.ptr, &mut ptr);
+            let value = vt::Value { ptr };
+            if value.is_empty() {
+                None
+            } else {
+                Some(value)
+            }
+        }
+    }
+
+    pub fn set_default_value(&self, value: &vt::Value) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::sdf_AttributeSpecHandle_SetDefaultValue(self.ptr, value.ptr, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::DefaultValueSet {
+                path: self.path().text().to_string(),
+            })
+        }
+    }
+
+    pub fn clear_default_value(&self) {
+        unsafe {
+            ffi::sdf_AttributeSpecHandle_ClearDefaultValue(self.ptr);
+        }
+    }
+
+    pub fn time_samples(&self) -> Vec<f64> {
+        self.layer().list_time_samples_for_path(&self.path())
+    }
+
+    pub fn num_time_samples(&self) -> usize {
+        self.layer().num_time_samples_for_path(&self.path())
+    }
+
+    pub fn query_time_sample(&self, time: f64) -> Option<vt::Value> {
+        self.layer().query_time_sample(&self.path(), time)
+    }
+
+    pub fn set_time_sample(&self, time: f64, value: &vt::Value) -> Result<(), Error> {
+        self.layer().set_time_sample(&self.path(), time, value)
+    }
+
+    pub fn erase_time_sample(&self, time: f64) {
+        self.layer().erase_time_sample(&self.path(), time)
+    }
+}
+
+impl Drop for AttributeSpec {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_AttributeSpecHandle_dtor(self.ptr);
+        }
+    }
+}
+
+pub struct RelationshipSpec {
+    pub(crate) ptr: *mut ffi::sdf_RelationshipSpecHandle_t,
+}
+
+impl RelationshipSpec {
+    pub fn new(
+        owner: &PrimSpec,
+        name: &str,
+        custom: bool,
+        variability: Variability,
+    ) -> Result<RelationshipSpec, Error> {
+        let name_str = cpp::String::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_RelationshipSpec_New(
+                owner.ptr,
+                name_str.ptr,
+                custom,
+                variability.into(),
+                &mut ptr,
+            );
+            let spec = RelationshipSpec { ptr };
+            if spec.is_valid() {
+                Ok(spec)
+            } else {
+                Err(Error::SpecCreate {
+                    path: format!("{}.{}", owner.path().text(), name),
+                })
+            }
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_RelationshipSpecHandle_is_valid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn path(&self) -> Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_RelationshipSpecHandle_GetPath(self.ptr, &mut ptr);
+            Path { ptr }
+        }
+    }
+
+    pub fn layer(&self) -> LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_RelationshipSpecHandle_GetLayer(self.ptr, &mut ptr);
+            LayerHandle { ptr }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_RelationshipSpecHandle_GetName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_RelationshipSpecHandle_IsCustom(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// The list of target path edits authored on this relationship.
+    pub fn target_path_list(&self) -> PathEditorProxy {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_RelationshipSpecHandle_GetTargetPathList(self.ptr, &mut ptr);
+            PathEditorProxy { ptr }
+        }
+    }
+}
+
+impl Drop for RelationshipSpec {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_RelationshipSpecHandle_dtor(self.ptr);
+        }
+    }
+}
+
+/// Edits a list-op valued field of paths in a spec, such as relationship targets.
+pub struct PathEditorProxy {
+    pub(crate) ptr: *mut ffi::sdf_PathEditorProxy_t,
+}
+
+impl PathEditorProxy {
+    pub fn is_explicit(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_PathEditorProxy_IsExplicit(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn explicit_items(&self) -> PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathEditorProxy_GetExplicitItems(self.ptr, &mut ptr);
+            PathVector { ptr }
+        }
+    }
+
+    pub fn prepended_items(&self) -> PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathEditorProxy_GetPrependedItems(self.ptr, &mut ptr);
+            PathVector { ptr }
+        }
+    }
+
+    pub fn appended_items(&self) -> PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathEditorProxy_GetAppendedItems(self.ptr, &mut ptr);
+            PathVector { ptr }
+        }
+    }
+
+    pub fn deleted_items(&self) -> PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathEditorProxy_GetDeletedItems(self.ptr, &mut ptr);
+            PathVector { ptr }
+        }
+    }
+
+    /// All paths that are explicitly set, prepended or appended.
+    pub fn added_or_explicit_items(&self) -> PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PathEditorProxy_GetAddedOrExplicitItems(self.ptr, &mut ptr);
+            PathVector { ptr }
+        }
+    }
+
+    pub fn set_explicit_items(&self, items: &PathVector) {
+        unsafe {
+            ffi::sdf_PathEditorProxy_SetExplicitItems(self.ptr, items.ptr);
+        }
+    }
+
+    pub fn prepend(&self, path: &Path) {
+        unsafe {
+            ffi::sdf_PathEditorProxy_Prepend(self.ptr, path.ptr);
+        }
+    }
+
+    pub fn append(&self, path: &Path) {
+        unsafe {
+            ffi::sdf_PathEditorProxy_Append(self.ptr, path.ptr);
+        }
+    }
+
+    pub fn remove(&self, path: &Path) {
+        unsafe {
+            ffi::sdf_PathEditorProxy_Remove(self.ptr, path.ptr);
+        }
+    }
+
+    pub fn clear_edits(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_PathEditorProxy_ClearEdits(self.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Drop for PathEditorProxy {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_PathEditorProxy_dtor(self.ptr);
+        }
+    }
+}
+
+/// A reference to the prim at `prim_path` in the layer at `asset_path`. An empty asset path
+/// refers to the current layer stack, and an empty prim path to the target's default prim.
+pub struct Reference {
+    pub(crate) ptr: *mut ffi::sdf_Reference_t,
+}
+
+impl Reference {
+    pub fn new(asset_path: &str, prim_path: Option<&Path>, layer_offset: &LayerOffset) -> Self {
+        let asset_path = cpp::String::new(asset_path);
+        let prim_path = prim_path.cloned().unwrap_or_else(Path::empty);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Reference_new(
+                asset_path.ptr,
+                prim_path.ptr,
+                layer_offset as *const LayerOffset as *const ffi::sdf_LayerOffset_t,
+                &mut ptr,
+            );
+            Reference { ptr }
+        }
+    }
+
+    pub fn asset_path(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Reference_GetAssetPath(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn prim_path(&self) -> PathRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Reference_GetPrimPath(self.ptr, &mut ptr);
+            PathRef::from_ptr(ptr)
+        }
+    }
+
+    pub fn layer_offset(&self) -> LayerOffset {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Reference_GetLayerOffset(self.ptr, &mut ptr);
+            *(ptr as *const LayerOffset)
+        }
+    }
+}
+
+impl Clone for Reference {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Reference_copy(self.ptr, &mut ptr);
+            Reference { ptr }
+        }
+    }
+}
+
+impl PartialEq for Reference {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Reference_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl fmt::Debug for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reference")
+            .field("asset_path", &self.asset_path())
+            .field("prim_path", &self.prim_path().text())
+            .field("layer_offset", &self.layer_offset())
+            .finish()
+    }
+}
+
+impl Drop for Reference {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_Reference_dtor(self.ptr);
+        }
+    }
+}
+
+/// Temporary `SdfReferenceVector` built from a slice.
+pub(crate) struct ReferenceVector {
+    pub(crate) ptr: *mut ffi::sdf_ReferenceVector_t,
+}
+
+impl ReferenceVector {
+    pub(crate) fn new(items: &[Reference]) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_ReferenceVector_default(&mut ptr);
+            for item in items {
+                ffi::sdf_ReferenceVector_push_back(ptr, item.ptr);
+            }
+            ReferenceVector { ptr }
+        }
+    }
+}
+
+impl Drop for ReferenceVector {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_ReferenceVector_dtor(self.ptr);
+        }
+    }
+}
+
+/// A payload to the prim at `prim_path` in the layer at `asset_path`. An empty asset path
+/// refers to the current layer stack, and an empty prim path to the target's default prim.
+pub struct Payload {
+    pub(crate) ptr: *mut ffi::sdf_Payload_t,
+}
+
+impl Payload {
+    pub fn new(asset_path: &str, prim_path: Option<&Path>, layer_offset: &LayerOffset) -> Self {
+        let asset_path = cpp::String::new(asset_path);
+        let prim_path = prim_path.cloned().unwrap_or_else(Path::empty);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Payload_new(
+                asset_path.ptr,
+                prim_path.ptr,
+                layer_offset as *const LayerOffset as *const ffi::sdf_LayerOffset_t,
+                &mut ptr,
+            );
+            Payload { ptr }
+        }
+    }
+
+    pub fn asset_path(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Payload_GetAssetPath(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+
+    pub fn prim_path(&self) -> PathRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Payload_GetPrimPath(self.ptr, &mut ptr);
+            PathRef::from_ptr(ptr)
+        }
+    }
+
+    pub fn layer_offset(&self) -> LayerOffset {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::sdf_Payload_GetLayerOffset(self.ptr, &mut ptr);
+            *(ptr as *const LayerOffset)
+        }
+    }
+}
+
+impl Clone for Payload {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_Payload_copy(self.ptr, &mut ptr);
+            Payload { ptr }
+        }
+    }
+}
+
+impl PartialEq for Payload {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::sdf_Payload_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Payload")
+            .field("asset_path", &self.asset_path())
+            .field("prim_path", &self.prim_path().text())
+            .field("layer_offset", &self.layer_offset())
+            .finish()
+    }
+}
+
+impl Drop for Payload {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_Payload_dtor(self.ptr);
+        }
+    }
+}
+
+/// Temporary `SdfPayloadVector` built from a slice.
+pub(crate) struct PayloadVector {
+    pub(crate) ptr: *mut ffi::sdf_PayloadVector_t,
+}
+
+impl PayloadVector {
+    pub(crate) fn new(items: &[Payload]) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_PayloadVector_default(&mut ptr);
+            for item in items {
+                ffi::sdf_PayloadVector_push_back(ptr, item.ptr);
+            }
+            PayloadVector { ptr }
+        }
+    }
+}
+
+impl Drop for PayloadVector {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sdf_PayloadVector_dtor(self.ptr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_path;
+
+    #[test]
+    fn path_parsing() {
+        assert!(Path::new("/World/Cube").is_ok());
+        assert!(Path::new("/World/Cube.size").is_ok());
+        assert!(matches!(Path::new("/World/"), Err(Error::InvalidPath { .. })));
+        assert!(matches!(Path::new("not a path"), Err(Error::InvalidPath { .. })));
+        assert!(Path::empty().is_empty());
+        assert!(Path::absolute_root().is_absolute_root());
+    }
+
+    #[test]
+    fn path_queries() {
+        let prim = Path::new("/World/Cube").unwrap();
+        assert!(prim.is_absolute());
+        assert!(prim.is_prim_path());
+        assert!(!prim.is_property_path());
+        assert_eq!(prim.name(), "Cube");
+        assert_eq!(prim.name_token().text(), "Cube");
+
+        let prop = Path::new("/World/Cube.size").unwrap();
+        assert!(prop.is_property_path());
+        assert_eq!(prop.name(), "size");
+        assert_eq!(prop.prim_path().text(), "/World/Cube");
+        assert_eq!(prop.parent_path().text(), "/World/Cube");
+
+        assert!(!Path::new("World").unwrap().is_absolute());
+
+        assert!(Path::is_valid_identifier("Cube_1"));
+        assert!(!Path::is_valid_identifier("1Cube"));
+        assert!(!Path::is_valid_identifier("a b"));
+    }
+
+    #[test]
+    fn path_append() {
+        let world = Path::new("/World").unwrap();
+        assert_eq!(world.append_child("Cube").unwrap().text(), "/World/Cube");
+        assert_eq!(world.append_property("size").unwrap().text(), "/World.size");
+        assert_eq!(
+            world.append_variant_selection("shape", "round").unwrap().text(),
+            "/World{shape=round}"
+        );
+        assert!(matches!(
+            world.append_child("not valid"),
+            Err(Error::InvalidPath { .. })
+        ));
+        assert!(world.append_property("size").unwrap().append_child("X").is_err());
+    }
+
+    #[test]
+    fn path_prefixes() {
+        let cube = Path::new("/World/Geo/Cube").unwrap();
+        let sphere = Path::new("/World/Geo/Sphere.radius").unwrap();
+        let geo = Path::new("/World/Geo").unwrap();
+
+        assert!(cube.has_prefix(&geo));
+        assert!(cube.has_prefix(&cube));
+        assert!(!geo.has_prefix(&cube));
+
+        assert_eq!(cube.common_prefix(&sphere).text(), "/World/Geo");
+
+        let moved = sphere.replace_prefix(&geo, &Path::new("/Other").unwrap());
+        assert_eq!(moved.text(), "/Other/Sphere.radius");
+
+        let unrelated = Path::new("/Else").unwrap();
+        assert_eq!(unrelated.replace_prefix(&geo, &cube).text(), "/Else");
+    }
+
+    #[test]
+    fn path_value_semantics() {
+        use std::collections::HashMap;
+
+        let a: Path = "/World/Cube".parse().unwrap();
+        let b = Path::new("/World/Cube").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.clone(), b);
+        assert_ne!(a, Path::new("/World").unwrap());
+        assert_eq!(format!("{:?}", a), "Path(\"/World/Cube\")");
+        assert!("/World/".parse::<Path>().is_err());
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        map.insert(b, 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&Path::new("/World/Cube").unwrap()], 2);
+    }
+
+    #[test]
+    fn path_ordering() {
+        let mut paths: Vec<Path> = ["/b", "/a/c", "/a", "/a.x"]
+            .iter()
+            .map(|p| Path::new(p).unwrap())
+            .collect();
+        paths.sort();
+        let texts: Vec<&str> = paths.iter().map(|p| p.text()).collect();
+        assert_eq!(texts[0], "/a");
+        assert_eq!(texts[3], "/b");
+        assert!(Path::new("/a").unwrap() < Path::new("/a/c").unwrap());
+    }
+
+    #[test]
+    fn path_vector_from_iterator() {
+        let paths: PathVector = ["/a", "/b/c"]
+            .iter()
+            .map(|p| Path::new(p).unwrap())
+            .collect();
+        assert_eq!(paths.size(), 2);
+        assert_eq!(paths.at(1).text(), "/b/c");
+
+        let texts: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+        assert_eq!(texts, ["/a", "/b/c"]);
+    }
+
+    #[test]
+    fn prim_specs() {
+        let layer = Layer::create_anonymous("prim_specs");
+        let handle = layer.as_handle();
+
+        let world = PrimSpec::new(&handle, "World", Specifier::Def, "Xform").unwrap();
+        let cube = PrimSpec::new_child(&world, "Cube", Specifier::Over, "").unwrap();
+        assert_eq!(world.specifier().unwrap(), Specifier::Def);
+        assert_eq!(cube.specifier().unwrap(), Specifier::Over);
+        assert_eq!(world.type_name().text(), "Xform");
+
+        cube.set_specifier(Specifier::Class);
+        assert_eq!(cube.specifier().unwrap(), Specifier::Class);
+
+        let found = layer
+            .get_prim_at_path(&Path::new("/World/Cube").unwrap())
+            .unwrap();
+        assert_eq!(found.name(), "Cube");
+        assert_eq!(world.name_children().len(), 1);
+        assert_eq!(layer.root_prims().len(), 1);
+
+        assert!(matches!(
+            layer.get_prim_at_path(&Path::new("/Missing").unwrap()),
+            Err(Error::NoSpecAtPath { .. })
+        ));
+        assert!(matches!(
+            PrimSpec::new(&handle, "not valid", Specifier::Def, ""),
+            Err(Error::SpecCreate { .. })
+        ));
+    }
+
+    #[test]
+    fn attribute_specs() {
+        let layer = Layer::create_anonymous("attribute_specs");
+        let prim = PrimSpec::new(&layer.as_handle(), "Prim", Specifier::Def, "").unwrap();
+        let double = ValueTypeName::find("double").unwrap();
+
+        let attr = AttributeSpec::new(&prim, "size", &double, Variability::Uniform, true).unwrap();
+        assert_eq!(attr.variability().unwrap(), Variability::Uniform);
+        assert!(attr.is_custom());
+        assert_eq!(prim.attributes().len(), 1);
+
+        assert!(!attr.has_default_value());
+        attr.set_default_value(&vt::Value::from_member(&2.0f64))
+            .unwrap();
+        assert_eq!(attr.default_value().unwrap().get::<f64>(), Some(&2.0));
+
+        attr.set_time_sample(1.0, &vt::Value::from_member(&3.0f64))
+            .unwrap();
+        attr.set_time_sample(2.0, &vt::Value::from_member(&4.0f64))
+            .unwrap();
+        assert_eq!(attr.time_samples(), vec![1.0, 2.0]);
+        assert_eq!(
+            attr.query_time_sample(2.0).unwrap().get::<f64>(),
+            Some(&4.0)
+        );
+        attr.erase_time_sample(1.0);
+        assert_eq!(attr.num_time_samples(), 1);
+
+        let rel = RelationshipSpec::new(&prim, "target", false, Variability::Uniform).unwrap();
+        assert!(!rel.is_custom());
+        assert_eq!(prim.relationships().len(), 1);
+
+        prim.remove_property("size").unwrap();
+        assert!(prim.attributes().is_empty());
+    }
+
+    #[test]
+    fn spec_edit_errors() {
+        let layer = Layer::create_anonymous("spec_edit_errors");
+        let prim = PrimSpec::new(&layer.as_handle(), "Prim", Specifier::Def, "").unwrap();
+        let double = ValueTypeName::find("double").unwrap();
+        let attr = AttributeSpec::new(&prim, "size", &double, Variability::Varying, false).unwrap();
+        let text = vt::Value::from_member(&"text".to_string());
+
+        assert!(matches!(
+            attr.set_default_value(&text),
+            Err(Error::DefaultValueSet { .. })
+        ));
+        assert!(matches!(
+            attr.set_time_sample(1.0, &text),
+            Err(Error::TimeSampleSet { .. })
+        ));
+        assert_eq!(attr.num_time_samples(), 0);
+
+        let missing = Path::new("/Prim.missing").unwrap();
+        let err = layer
+            .set_time_sample(&missing, 1.0, &vt::Value::from_member(&1.0f64))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to set a time sample at 1 on \"/Prim.missing\""
+        );
+
+        assert!(matches!(
+            prim.remove_property("missing"),
+            Err(Error::NoSpecAtPath { .. })
+        ));
+        assert!(matches!(
+            prim.remove_property("not valid"),
+            Err(Error::InvalidPath { .. })
+        ));
+        assert_eq!(prim.attributes().len(), 1);
+    }
+
+    const USDA: &str = "#usda 1.0\n\ndef Xform \"World\"\n{\n    double size = 2\n}\n";
+
+    #[test]
+    fn layer_string_round_trip() {
+        let layer = Layer::create_anonymous("string_round_trip");
+        layer.import_from_string(USDA).unwrap();
+        assert!(layer
+            .get_prim_at_path(&Path::new("/World").unwrap())
+            .is_ok());
+
+        let text = layer.export_to_string().unwrap();
+        assert!(text.contains("def Xform \"World\""));
+        assert!(text.contains("double size = 2"));
+
+        let copy = Layer::create_anonymous("string_round_trip_copy");
+        copy.import_from_string(&text).unwrap();
+        assert_eq!(copy.export_to_string().unwrap(), text);
+    }
+
+    #[test]
+    fn layer_import_invalid_string_is_an_error() {
+        let layer = Layer::create_anonymous("import_invalid");
+        assert!(matches!(
+            layer.import_from_string("not usda"),
+            Err(Error::LayerImport { .. })
+        ));
+    }
+
+    #[test]
+    fn layer_export_and_open() {
+        let path = temp_path("layer_export.usda");
+        let layer = Layer::create_anonymous("export");
+        layer.import_from_string(USDA).unwrap();
+        layer.export(&path, "", &HashMap::new()).unwrap();
+
+        let opened = Layer::find_or_open(&path, &HashMap::new()).unwrap();
+        assert!(opened
+            .get_prim_at_path(&Path::new("/World").unwrap())
+            .is_ok());
+        assert!(!opened.is_dirty());
+
+        opened.clear();
+        assert!(opened.is_dirty());
+        opened.reload(false).unwrap();
+        assert_eq!(opened.root_prims().len(), 1);
+
+        let anonymous = Layer::open_as_anonymous(&path).unwrap();
+        assert_eq!(anonymous.root_prims().len(), 1);
+    }
+
+    #[test]
+    fn layer_open_missing_file_is_an_error() {
+        let path = temp_path("layer_missing.usda");
+        let err = Layer::find_or_open(&path, &HashMap::new()).err().unwrap();
+        assert!(matches!(err, Error::LayerOpen { .. }));
+        assert!(err.to_string().starts_with("failed to open layer"));
+    }
+
+    #[test]
+    fn error_display() {
+        let err = Error::InvalidPath {
+            path: "a b".to_string(),
+        };
+        assert_eq!(err.to_string(), "invalid path \"a b\"");
+
+        let err: Box<dyn std::error::Error> = Box::new(Error::UnknownEnumValue {
+            enum_name: "SdfSpecifier",
+            value: 7,
+        });
+        assert_eq!(err.to_string(), "unknown SdfSpecifier value 7");
+    }
+
+    #[test]
+    fn layer_offset_composition() {
+        let a = LayerOffset::new(10.0, 2.0);
+        let b = LayerOffset::new(-3.0, 0.5);
+        assert_eq!(a.apply(1.0), 12.0);
+        assert_eq!((a * b).apply(4.0), a.apply(b.apply(4.0)));
+        assert_eq!(a * LayerOffset::default(), a);
+        assert_eq!(LayerOffset::default() * a, a);
+
+        let inv = a.inverse();
+        assert_eq!(inv, LayerOffset::new(-5.0, 0.5));
+        assert!((a * inv).is_identity());
+        assert_eq!(inv.apply(a.apply(7.0)), 7.0);
+        assert_eq!(LayerOffset::default().inverse(), LayerOffset::default());
+        assert_eq!(LayerOffset::new(1.0, 0.0).inverse().scale, f64::INFINITY);
+    }
+
+    #[test]
+    fn sublayers() {
+        let layer = Layer::create_anonymous("sublayers");
+        layer.insert_sublayer_path("b.usda", None).unwrap();
+        layer.insert_sublayer_path("a.usda", Some(0)).unwrap();
+        layer.insert_sublayer_path("c.usda", Some(2)).unwrap();
+        assert_eq!(layer.sublayer_paths(), ["a.usda", "b.usda", "c.usda"]);
+
+        let err = layer.insert_sublayer_path("d.usda", Some(5)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SublayerIndex {
+                index: 5,
+                bound: 4,
+                ..
+            }
+        ));
+        assert!(err.to_string().ends_with("expected less than 4"));
+        assert!(matches!(
+            layer.sublayer_offset(3),
+            Err(Error::SublayerIndex { bound: 3, .. })
+        ));
+        assert!(matches!(
+            layer.remove_sublayer_path(usize::MAX),
+            Err(Error::SublayerIndex { .. })
+        ));
+
+        let offset = LayerOffset::new(5.0, 1.0);
+        layer.set_sublayer_offset(&offset, 1).unwrap();
+        assert_eq!(layer.sublayer_offset(1).unwrap(), offset);
+        assert!(layer.sublayer_offset(0).unwrap().is_identity());
+        assert_eq!(layer.sublayer_offsets().len(), 3);
+        assert!(layer.set_sublayer_offset(&offset, 3).is_err());
+        assert!(layer.sublayer_offset(3).is_err());
+
+        layer.remove_sublayer_path(0).unwrap();
+        assert_eq!(layer.sublayer_paths(), ["b.usda", "c.usda"]);
+        assert_eq!(layer.sublayer_offset(0).unwrap(), offset);
+
+        layer.set_sublayer_paths(&["c.usda", "b.usda"]);
+        assert_eq!(layer.num_sublayer_paths(), 2);
+    }
+
+    #[test]
+    fn value_type_name_tf_type_ignores_roles() {
+        use vt::ValueMember;
+
+        let float3 = ValueTypeName::find("float3").unwrap();
+        let color3f = ValueTypeName::find("color3f").unwrap();
+        assert_eq!(float3.tf_type(), color3f.tf_type());
+        assert_eq!(float3.tf_type(), glam::Vec3::tf_type());
+        assert_eq!(glam::Vec3::tf_type().type_name(), "GfVec3f");
+        assert!(color3f.holds_type::<glam::Vec3>());
+        assert!(!color3f.holds_type::<glam::DVec3>());
+    }
+}
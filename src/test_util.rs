@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+/// A fresh path in a per-process temporary directory. Any existing file is removed.
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bbl-usd-rs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
@@ -1,6 +1,11 @@
 use crate::ffi;
+use std::cmp::Ordering;
+use std::convert::Infallible;
 use std::ffi::{CStr, CString};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
 
 pub struct Token {
     pub(crate) ptr: *mut ffi::tf_Token_t,
@@ -17,7 +22,7 @@ impl Token {
         }
     }
 
-    pub fn text(&self) -> &'static str {
+    pub fn text(&self) -> &str {
         unsafe {
             let mut ptr = std::ptr::null();
             ffi::tf_Token_GetText(self.ptr, &mut ptr);
@@ -32,6 +37,77 @@ impl fmt::Display for Token {
     }
 }
 
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Token({:?})", self.text())
+    }
+}
+
+impl FromStr for Token {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Token::new(s))
+    }
+}
+
+impl Clone for Token {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Token_copy(self.ptr, &mut ptr);
+            Token { ptr }
+        }
+    }
+}
+
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::tf_Token_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Eq for Token {}
+
+impl Hash for Token {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe {
+            let mut result = 0;
+            ffi::tf_Token_Hash(self.ptr, &mut result);
+            state.write_usize(result);
+        }
+    }
+}
+
+impl PartialOrd for Token {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Tokens are ordered lexicographically by their text, as with `TfToken::operator<`.
+impl Ord for Token {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            unsafe {
+                let mut less = false;
+                ffi::tf_Token_op_lt(self.ptr, other.ptr, &mut less);
+                if less {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            }
+        }
+    }
+}
+
 impl Drop for Token {
     fn drop(&mut self) {
         unsafe {
@@ -46,11 +122,25 @@ impl AsRef<str> for Token {
     }
 }
 
-pub struct TokenRef {
+/// A borrowed [`Token`] owned by another object.
+#[repr(transparent)]
+pub struct TokenRef<'a> {
     pub(crate) ptr: *const ffi::tf_Token_t,
+    _marker: PhantomData<&'a Token>,
+}
+
+impl<'a> TokenRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid object that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::tf_Token_t) -> Self {
+        TokenRef {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
 }
 
-impl std::ops::Deref for TokenRef {
+impl<'a> std::ops::Deref for TokenRef<'a> {
     type Target = Token;
 
     fn deref(&self) -> &Self::Target {
@@ -58,15 +148,91 @@ impl std::ops::Deref for TokenRef {
     }
 }
 
-impl fmt::Display for TokenRef {
+impl fmt::Display for TokenRef<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.text())
     }
 }
 
-impl AsRef<str> for TokenRef {
+impl AsRef<str> for TokenRef<'_> {
     fn as_ref(&self) -> &str {
         self.text()
     }
 }
 
+
+/// A C++ type registered with the `TfType` system, such as the type held by a `VtValue`.
+pub struct Type {
+    pub(crate) ptr: *mut ffi::tf_Type_t,
+}
+
+impl Type {
+    pub fn type_name(&self) -> &str {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::tf_Type_GetTypeName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
+        }
+    }
+}
+
+impl fmt::Debug for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Type({:?})", self.type_name())
+    }
+}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::tf_Type_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Eq for Type {}
+
+impl Drop for Type {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::tf_Type_dtor(self.ptr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn token_value_semantics() {
+        let a = Token::new("alpha");
+        let b: Token = "alpha".parse().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.clone(), b);
+        assert_ne!(a, Token::new("beta"));
+        assert_eq!(format!("{:?}", a), "Token(\"alpha\")");
+        assert_eq!(a.to_string(), "alpha");
+
+        let set: HashSet<Token> = [a.clone(), b, Token::new("beta")].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a));
+    }
+
+    #[test]
+    fn token_ordering_is_lexicographic() {
+        let sorted: Vec<String> = ["c", "a", "b", "a"]
+            .iter()
+            .map(|s| Token::new(s))
+            .collect::<BTreeSet<_>>()
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        assert_eq!(sorted, ["a", "b", "c"]);
+    }
+}
@@ -1,29 +1,157 @@
+use crate::ar;
+use crate::cpp;
 use crate::ffi;
 use crate::sdf;
 use crate::tf;
 use crate::vt;
 
+use std::collections::BTreeMap;
+use std::convert::Infallible;
 use std::ffi::{CStr, CString};
+use std::fmt;
 use std::path::Path;
 
 #[derive(Debug)]
 pub enum Error {
     StageOpen { filename: String },
+    StageCreate { filename: String },
+    StageSave { identifier: String },
+    StageExport { filename: String },
     NoPrimAtPath { path: String },
+    NoDefaultPrim,
+    PrimDefine { path: String, type_name: String },
+    PrimOverride { path: String },
+    PrimCreateClass { path: String },
+    PrimRemove { path: String },
+    AttributeCreate { path: String },
+    AttributeSet { path: String },
+    AttributeClear { path: String },
+    CompositionEdit { path: String, arc: &'static str },
+    PrimNotOnStage { path: String },
+    PrimSetActive { path: String },
+    MetadataSet { path: String, key: String },
+    MetadataClear { path: String, key: String },
+    NoAttributeAtPath { path: String },
+    RelationshipCreate { path: String },
+    NoRelationshipAtPath { path: String },
+    PropertyRemove { path: String },
+    VariantSetCreate { path: String, name: String },
+    NoVariantSet { path: String, name: String },
+    VariantSetEdit { path: String, name: String },
+    TypeMismatch { path: String, type_name: String, requested: &'static str },
+    NoValue { path: String },
+    Sdf(sdf::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::StageOpen { filename } => write!(f, "failed to open stage \"{filename}\""),
+            Error::StageCreate { filename } => {
+                write!(f, "failed to create stage \"{filename}\"")
+            }
+            Error::StageSave { identifier } => write!(f, "failed to save stage \"{identifier}\""),
+            Error::StageExport { filename } => {
+                write!(f, "failed to export stage to \"{filename}\"")
+            }
+            Error::NoPrimAtPath { path } => write!(f, "no prim at path \"{path}\""),
+            Error::NoDefaultPrim => write!(f, "stage has no default prim"),
+            Error::PrimDefine { path, type_name } => {
+                write!(f, "failed to define prim \"{path}\" of type \"{type_name}\"")
+            }
+            Error::PrimOverride { path } => write!(f, "failed to override prim \"{path}\""),
+            Error::PrimCreateClass { path } => {
+                write!(f, "failed to create class prim \"{path}\"")
+            }
+            Error::PrimRemove { path } => write!(f, "failed to remove prim \"{path}\""),
+            Error::AttributeCreate { path } => {
+                write!(f, "failed to create attribute \"{path}\"")
+            }
+            Error::AttributeSet { path } => write!(f, "failed to set attribute \"{path}\""),
+            Error::AttributeClear { path } => {
+                write!(f, "failed to clear attribute \"{path}\"")
+            }
+            Error::CompositionEdit { path, arc } => {
+                write!(f, "failed to edit {arc} on prim \"{path}\"")
+            }
+            Error::PrimNotOnStage { path } => {
+                write!(f, "prim \"{path}\" is not on the given stage")
+            }
+            Error::PrimSetActive { path } => {
+                write!(f, "failed to set active on prim \"{path}\"")
+            }
+            Error::MetadataSet { path, key } => {
+                write!(f, "failed to set metadata \"{key}\" on \"{path}\"")
+            }
+            Error::MetadataClear { path, key } => {
+                write!(f, "failed to clear metadata \"{key}\" on \"{path}\"")
+            }
+            Error::NoAttributeAtPath { path } => write!(f, "no attribute at path \"{path}\""),
+            Error::RelationshipCreate { path } => {
+                write!(f, "failed to create relationship \"{path}\"")
+            }
+            Error::NoRelationshipAtPath { path } => {
+                write!(f, "no relationship at path \"{path}\"")
+            }
+            Error::PropertyRemove { path } => write!(f, "failed to remove property \"{path}\""),
+            Error::VariantSetCreate { path, name } => {
+                write!(f, "failed to create variant set \"{name}\" on \"{path}\"")
+            }
+            Error::NoVariantSet { path, name } => {
+                write!(f, "no variant set \"{name}\" on \"{path}\"")
+            }
+            Error::VariantSetEdit { path, name } => {
+                write!(f, "failed to edit variant set \"{name}\" on \"{path}\"")
+            }
+            Error::TypeMismatch {
+                path,
+                type_name,
+                requested,
+            } => write!(
+                f,
+                "attribute \"{path}\" has type \"{type_name}\" but {requested} was requested"
+            ),
+            Error::NoValue { path } => write!(f, "attribute \"{path}\" has no value"),
+            Error::Sdf(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Sdf(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<sdf::Error> for Error {
+    fn from(value: sdf::Error) -> Self {
+        Error::Sdf(value)
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
 }
 
 pub struct Stage {}
 
 impl Stage {
-    pub fn open<P: AsRef<Path>>(filename: P) -> Result<StageRefPtr, Error> {
+    pub fn open<P: AsRef<Path>>(
+        filename: P,
+        load_set: InitialLoadSet,
+    ) -> Result<StageRefPtr, Error> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            let initial_load_set = ffi::usd_StageInitialLoadSet::usd_StageInitialLoadSet_LoadAll;
             let filename = filename.as_ref().to_string_lossy().to_string();
             let c_filename = CString::new(filename.clone()).unwrap();
             ffi::usd_Stage_Open(
                 c_filename.as_ptr(),
-                initial_load_set,
+                load_set.into(),
                 &mut ptr,
             );
 
@@ -38,146 +166,2662 @@ impl Stage {
         }
     }
 
-    pub fn with_layer(layer: &sdf::LayerHandle) -> StageRefPtr {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            let initial_load_set = ffi::usd_StageInitialLoadSet::usd_StageInitialLoadSet_LoadAll;
-            ffi::usd_Stage_Open_at_root(
-                layer.ptr,
-                initial_load_set,
-                &mut ptr,
-            );
-            StageRefPtr { ptr }
+    /// Open the stage at `filename`, composing only the prims included by `mask`.
+    pub fn open_masked<P: AsRef<Path>>(
+        filename: P,
+        mask: &StagePopulationMask,
+        load_set: InitialLoadSet,
+    ) -> Result<StageRefPtr, Error> {
+        OpenOptions::new(filename).mask(mask).load_set(load_set).open()
+    }
+
+    /// Open a stage as configured by `options`.
+    pub fn open_with(options: OpenOptions) -> Result<StageRefPtr, Error> {
+        options.open()
+    }
+
+    pub fn with_layer(layer: &sdf::LayerHandle, load_set: InitialLoadSet) -> StageRefPtr {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Stage_Open_at_root(
+                layer.ptr,
+                load_set.into(),
+                &mut ptr,
+            );
+            StageRefPtr { ptr }
+        }
+    }
+
+    pub fn create_new<P: AsRef<Path>>(
+        filename: P,
+        load_set: InitialLoadSet,
+    ) -> Result<StageRefPtr, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            let filename = filename.as_ref().to_string_lossy().to_string();
+            let c_filename = CString::new(filename.clone()).unwrap();
+            ffi::usd_Stage_CreateNew(
+                c_filename.as_ptr(),
+                load_set.into(),
+                &mut ptr,
+            );
+
+            let mut is_invalid = true;
+            ffi::usd_StageRefPtr_is_invalid(ptr, &mut is_invalid);
+
+            if is_invalid {
+                Err(Error::StageCreate { filename })
+            } else {
+                Ok(StageRefPtr { ptr })
+            }
+        }
+    }
+
+    pub fn create_in_memory(load_set: InitialLoadSet) -> StageRefPtr {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Stage_CreateInMemory(load_set.into(), &mut ptr);
+            StageRefPtr { ptr }
+        }
+    }
+}
+
+/// Options for opening a stage with [`Stage::open_with`].
+///
+/// If no session layer is given a new anonymous one is created, and if no resolver context is
+/// given the resolver's default context for `filename` is used, as [`Stage::open`] does.
+pub struct OpenOptions<'a> {
+    filename: String,
+    session_layer: Option<&'a sdf::LayerHandle>,
+    resolver_context: Option<&'a ar::ResolverContext>,
+    mask: Option<&'a StagePopulationMask>,
+    load_set: InitialLoadSet,
+}
+
+impl<'a> OpenOptions<'a> {
+    pub fn new<P: AsRef<Path>>(filename: P) -> Self {
+        OpenOptions {
+            filename: filename.as_ref().to_string_lossy().to_string(),
+            session_layer: None,
+            resolver_context: None,
+            mask: None,
+            load_set: InitialLoadSet::default(),
+        }
+    }
+
+    pub fn session_layer(mut self, layer: &'a sdf::LayerHandle) -> Self {
+        self.session_layer = Some(layer);
+        self
+    }
+
+    pub fn resolver_context(mut self, context: &'a ar::ResolverContext) -> Self {
+        self.resolver_context = Some(context);
+        self
+    }
+
+    pub fn mask(mut self, mask: &'a StagePopulationMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn load_set(mut self, load_set: InitialLoadSet) -> Self {
+        self.load_set = load_set;
+        self
+    }
+
+    pub fn open(self) -> Result<StageRefPtr, Error> {
+        // The stage takes its own reference to the session layer, but ours has to outlive the
+        // open call.
+        let anonymous_session_layer;
+        let anonymous_session_handle;
+        let session_layer = match self.session_layer {
+            Some(layer) => layer,
+            None => {
+                anonymous_session_layer = sdf::Layer::create_anonymous("session.usda");
+                anonymous_session_handle = anonymous_session_layer.as_handle();
+                &anonymous_session_handle
+            }
+        };
+
+        let default_context;
+        let resolver_context = match self.resolver_context {
+            Some(context) => context,
+            None => {
+                default_context = ar::ResolverContext::default_for_asset(&self.filename);
+                &default_context
+            }
+        };
+
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            let c_filename = CString::new(self.filename.clone()).unwrap();
+            match self.mask {
+                Some(mask) => ffi::usd_Stage_OpenMasked_with_session_layer_and_context(
+                    c_filename.as_ptr(),
+                    session_layer.ptr,
+                    resolver_context.ptr,
+                    mask.ptr,
+                    self.load_set.into(),
+                    &mut ptr,
+                ),
+                None => ffi::usd_Stage_Open_with_session_layer_and_context(
+                    c_filename.as_ptr(),
+                    session_layer.ptr,
+                    resolver_context.ptr,
+                    self.load_set.into(),
+                    &mut ptr,
+                ),
+            };
+
+            let mut is_invalid = true;
+            ffi::usd_StageRefPtr_is_invalid(ptr, &mut is_invalid);
+
+            if is_invalid {
+                Err(Error::StageOpen {
+                    filename: self.filename,
+                })
+            } else {
+                Ok(StageRefPtr { ptr })
+            }
+        }
+    }
+}
+
+impl fmt::Debug for OpenOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenOptions")
+            .field("filename", &self.filename)
+            .field("mask", &self.mask)
+            .field("load_set", &self.load_set)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The set of prim paths, and their descendants, that a stage composes.
+pub struct StagePopulationMask {
+    pub(crate) ptr: *mut ffi::usd_StagePopulationMask_t,
+}
+
+impl StagePopulationMask {
+    /// An empty mask, which includes nothing.
+    pub fn new() -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_default(&mut ptr);
+            StagePopulationMask { ptr }
+        }
+    }
+
+    /// A mask that includes every prim.
+    pub fn all() -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_All(&mut ptr);
+            StagePopulationMask { ptr }
+        }
+    }
+
+    pub fn add(&mut self, path: &sdf::Path) -> &mut Self {
+        unsafe {
+            let mut dummy = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_Add(self.ptr, path.ptr, &mut dummy);
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StagePopulationMask_IsEmpty(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether `path` is included, or is an ancestor of an included path.
+    pub fn includes(&self, path: &sdf::Path) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StagePopulationMask_Includes(self.ptr, path.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether `path` and all of its descendants are included.
+    pub fn includes_subtree(&self, path: &sdf::Path) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StagePopulationMask_IncludesSubtree(self.ptr, path.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn paths(&self) -> sdf::PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_GetPaths(self.ptr, &mut ptr);
+            sdf::PathVector { ptr }
+        }
+    }
+
+    pub fn union(&self, other: &StagePopulationMask) -> StagePopulationMask {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_GetUnion(self.ptr, other.ptr, &mut ptr);
+            StagePopulationMask { ptr }
+        }
+    }
+
+    pub fn intersection(&self, other: &StagePopulationMask) -> StagePopulationMask {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_GetIntersection(self.ptr, other.ptr, &mut ptr);
+            StagePopulationMask { ptr }
+        }
+    }
+}
+
+impl Default for StagePopulationMask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for StagePopulationMask {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StagePopulationMask_copy(self.ptr, &mut ptr);
+            StagePopulationMask { ptr }
+        }
+    }
+}
+
+impl PartialEq for StagePopulationMask {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StagePopulationMask_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl fmt::Debug for StagePopulationMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.paths().iter().map(|p| p.text().to_string()))
+            .finish()
+    }
+}
+
+impl FromIterator<sdf::Path> for StagePopulationMask {
+    fn from_iter<I: IntoIterator<Item = sdf::Path>>(iter: I) -> Self {
+        let mut result = StagePopulationMask::new();
+        for path in iter {
+            result.add(&path);
+        }
+        result
+    }
+}
+
+impl Drop for StagePopulationMask {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_StagePopulationMask_dtor(self.ptr);
+        }
+    }
+}
+
+/// Which payloads are loaded when a stage is opened.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InitialLoadSet {
+    #[default]
+    LoadAll,
+    LoadNone,
+}
+
+impl From<InitialLoadSet> for ffi::usd_StageInitialLoadSet {
+    fn from(value: InitialLoadSet) -> Self {
+        match value {
+            InitialLoadSet::LoadAll => ffi::usd_StageInitialLoadSet::usd_StageInitialLoadSet_LoadAll,
+            InitialLoadSet::LoadNone => {
+                ffi::usd_StageInitialLoadSet::usd_StageInitialLoadSet_LoadNone
+            }
+        }
+    }
+}
+
+/// Whether loading or unloading a prim also affects its descendants.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LoadPolicy {
+    #[default]
+    WithDescendants,
+    WithoutDescendants,
+}
+
+impl From<LoadPolicy> for ffi::usd_LoadPolicy {
+    fn from(value: LoadPolicy) -> Self {
+        match value {
+            LoadPolicy::WithDescendants => ffi::usd_LoadPolicy::usd_LoadPolicy_UsdLoadWithDescendants,
+            LoadPolicy::WithoutDescendants => {
+                ffi::usd_LoadPolicy::usd_LoadPolicy_UsdLoadWithoutDescendants
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StageRefPtr {
+    pub(crate) ptr: *mut ffi::usd_StageRefPtr_t,
+}
+
+impl StageRefPtr {
+    pub fn pseudo_root(&self) -> Prim {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetPseudoRoot(self.ptr, &mut ptr);
+            Prim { ptr }
+        }
+    }
+
+    pub fn prim_at_path<P>(&self, path: P) -> Result<Prim, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        self._prim_at_path(&path)
+    }
+
+    fn _prim_at_path(&self, path: &sdf::Path) -> Result<Prim, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetPrimAtPath(self.ptr, path.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Prim_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Prim{ptr})
+            } else {
+                Err(Error::NoPrimAtPath { path: path.text().to_string() })
+            }
+        }
+    }
+
+    pub fn define_prim<P>(&self, path: P, type_name: &str) -> Result<Prim, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        let type_name_token = tf::Token::new(type_name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_DefinePrim(self.ptr, path.ptr, type_name_token.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Prim_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Prim { ptr })
+            } else {
+                ffi::usd_Prim_dtor(ptr);
+                Err(Error::PrimDefine {
+                    path: path.text().to_string(),
+                    type_name: type_name.to_string(),
+                })
+            }
+        }
+    }
+
+    pub fn override_prim<P>(&self, path: P) -> Result<Prim, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_OverridePrim(self.ptr, path.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Prim_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Prim { ptr })
+            } else {
+                ffi::usd_Prim_dtor(ptr);
+                Err(Error::PrimOverride { path: path.text().to_string() })
+            }
+        }
+    }
+
+    pub fn create_class_prim<P>(&self, path: P) -> Result<Prim, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_CreateClassPrim(self.ptr, path.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Prim_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Prim { ptr })
+            } else {
+                ffi::usd_Prim_dtor(ptr);
+                Err(Error::PrimCreateClass { path: path.text().to_string() })
+            }
+        }
+    }
+
+    /// Remove all scene description for the prim at `path` in the current edit target.
+    pub fn remove_prim<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        unsafe {
+            let mut result = false;
+            ffi::usd_StageRefPtr_RemovePrim(self.ptr, path.ptr, &mut result);
+
+            if result {
+                Ok(())
+            } else {
+                Err(Error::PrimRemove { path: path.text().to_string() })
+            }
+        }
+    }
+
+    pub fn default_prim(&self) -> Result<Prim, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetDefaultPrim(self.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Prim_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Prim { ptr })
+            } else {
+                ffi::usd_Prim_dtor(ptr);
+                Err(Error::NoDefaultPrim)
+            }
+        }
+    }
+
+    pub fn set_default_prim(&self, prim: &Prim) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetDefaultPrim(self.ptr, prim.ptr);
+        }
+    }
+
+    pub fn has_default_prim(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StageRefPtr_HasDefaultPrim(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn clear_default_prim(&self) {
+        unsafe {
+            ffi::usd_StageRefPtr_ClearDefaultPrim(self.ptr);
+        }
+    }
+
+    pub fn start_time_code(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::usd_StageRefPtr_GetStartTimeCode(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn set_start_time_code(&self, value: f64) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetStartTimeCode(self.ptr, value);
+        }
+    }
+
+    pub fn end_time_code(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::usd_StageRefPtr_GetEndTimeCode(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn set_end_time_code(&self, value: f64) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetEndTimeCode(self.ptr, value);
+        }
+    }
+
+    pub fn has_authored_time_code_range(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_StageRefPtr_HasAuthoredTimeCodeRange(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn time_codes_per_second(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::usd_StageRefPtr_GetTimeCodesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn set_time_codes_per_second(&self, value: f64) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetTimeCodesPerSecond(self.ptr, value);
+        }
+    }
+
+    pub fn frames_per_second(&self) -> f64 {
+        unsafe {
+            let mut result = 0.0;
+            ffi::usd_StageRefPtr_GetFramesPerSecond(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn set_frames_per_second(&self, value: f64) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetFramesPerSecond(self.ptr, value);
+        }
+    }
+
+    /// The stage's `documentation` metadata, composed from the session and root layers.
+    pub fn documentation(&self) -> String {
+        self._metadata("documentation")
+            .and_then(|value| value.as_string().map(|s| s.to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Author the stage's `documentation` metadata. Stage metadata can only be authored when
+    /// the edit target is the root or session layer.
+    pub fn set_documentation(&self, value: &str) -> Result<(), Error> {
+        self._set_metadata("documentation", &vt::Value::from_member(&value.to_string()))
+    }
+
+    /// The stage's `comment` metadata, composed from the session and root layers.
+    pub fn comment(&self) -> String {
+        self._metadata("comment")
+            .and_then(|value| value.as_string().map(|s| s.to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Author the stage's `comment` metadata. Stage metadata can only be authored when the
+    /// edit target is the root or session layer.
+    pub fn set_comment(&self, value: &str) -> Result<(), Error> {
+        self._set_metadata("comment", &vt::Value::from_member(&value.to_string()))
+    }
+
+    /// The stage's `customLayerData`, composed from the session and root layers.
+    pub fn custom_layer_data(&self) -> vt::Dictionary {
+        self._metadata("customLayerData")
+            .and_then(|value| value.get::<vt::Dictionary>().map(|dict| (*dict).clone()))
+            .unwrap_or_default()
+    }
+
+    /// Author the stage's `customLayerData`. Stage metadata can only be authored when the
+    /// edit target is the root or session layer.
+    pub fn set_custom_layer_data(&self, value: &vt::Dictionary) -> Result<(), Error> {
+        self._set_metadata("customLayerData", &vt::Value::from_member(value))
+    }
+
+    fn _metadata(&self, key: &str) -> Option<vt::Value> {
+        let key = tf::Token::new(key);
+        unsafe {
+            let value = vt::Value::new();
+            let mut result = false;
+            ffi::usd_StageRefPtr_GetMetadata(self.ptr, key.ptr, value.ptr, &mut result);
+            if result {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn _set_metadata(&self, key: &str, value: &vt::Value) -> Result<(), Error> {
+        let token = tf::Token::new(key);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_StageRefPtr_SetMetadata(self.ptr, token.ptr, value.ptr, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::MetadataSet {
+                path: "/".to_string(),
+                key: key.to_string(),
+            })
+        }
+    }
+
+    pub fn edit_target(&self) -> EditTarget {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetEditTarget(self.ptr, &mut ptr);
+            EditTarget { ptr }
+        }
+    }
+
+    /// Direct all subsequent authoring on this stage to `target`. The target's layer must be
+    /// in the stage's layer stack.
+    pub fn set_edit_target(&self, target: &EditTarget) {
+        unsafe {
+            ffi::usd_StageRefPtr_SetEditTarget(self.ptr, target.ptr);
+        }
+    }
+
+    /// Set the edit target to `target` until the returned guard is dropped.
+    pub fn edit_context(&self, target: &EditTarget) -> EditContext<'_> {
+        EditContext::new(self, target)
+    }
+
+    /// Load the prim at `path` and, depending on `policy`, its descendants.
+    pub fn load<P>(&self, path: P, policy: LoadPolicy) -> Result<Prim, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_Load(self.ptr, path.ptr, policy.into(), &mut ptr);
+            ffi::usd_Prim_dtor(ptr);
+        }
+        self._prim_at_path(&path)
+    }
+
+    /// Unload the prim at `path` and its descendants.
+    pub fn unload<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let path: sdf::Path = path.try_into()?;
+        unsafe {
+            ffi::usd_StageRefPtr_Unload(self.ptr, path.ptr);
+        }
+        Ok(())
+    }
+
+    /// Unload every path in `unload_set` then load every path in `load_set`, recomposing the
+    /// stage once.
+    pub fn load_and_unload(
+        &self,
+        load_set: &[sdf::Path],
+        unload_set: &[sdf::Path],
+        policy: LoadPolicy,
+    ) {
+        let load_set = sdf::PathSet::new(load_set);
+        let unload_set = sdf::PathSet::new(unload_set);
+        unsafe {
+            ffi::usd_StageRefPtr_LoadAndUnload(
+                self.ptr,
+                load_set.ptr,
+                unload_set.ptr,
+                policy.into(),
+            );
+        }
+    }
+
+    /// The paths of all loaded prims that have payloads.
+    pub fn get_loaded_paths(&self) -> Vec<sdf::Path> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetLoadSet(self.ptr, &mut ptr);
+            sdf::PathSet::from_ptr(ptr).to_vec()
+        }
+    }
+
+    /// The paths of all prims at or under `root` that have payloads, whether loaded or not.
+    pub fn find_loadable<P>(&self, root: P) -> Result<Vec<sdf::Path>, Error>
+    where
+        P: TryInto<sdf::Path>,
+        Error: From<P::Error>,
+    {
+        let root: sdf::Path = root.try_into()?;
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_FindLoadable(self.ptr, root.ptr, &mut ptr);
+            Ok(sdf::PathSet::from_ptr(ptr).to_vec())
+        }
+    }
+
+    /// Traverse the stage's active, loaded, defined, non-abstract prims, depth first. The
+    /// pseudo-root is not included.
+    pub fn traverse(&self) -> PrimRange {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_Traverse(self.ptr, &mut ptr);
+            PrimRange::_from_ptr(ptr)
+        }
+    }
+
+    /// Traverse the stage's prims that pass `predicate`, depth first.
+    pub fn traverse_filtered<P: Into<PrimFlagsPredicate>>(&self, predicate: P) -> PrimRange {
+        let predicate = predicate.into()._to_ffi();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_Traverse_with_predicate(self.ptr, predicate.ptr, &mut ptr);
+            PrimRange::_from_ptr(ptr)
+        }
+    }
+
+    /// Traverse every prim on the stage, depth first.
+    pub fn traverse_all(&self) -> PrimRange {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_TraverseAll(self.ptr, &mut ptr);
+            PrimRange::_from_ptr(ptr)
+        }
+    }
+
+    pub fn as_weak(&self) -> StageWeakPtr {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_as_weak(self.ptr, &mut ptr);
+            StageWeakPtr { ptr }
+        }
+    }
+
+    pub fn root_layer(&self) -> sdf::LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetRootLayer(self.ptr, &mut ptr);
+            sdf::LayerHandle { ptr }
+        }
+    }
+
+    pub fn session_layer(&self) -> sdf::LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetSessionLayer(self.ptr, &mut ptr);
+            sdf::LayerHandle { ptr }
+        }
+    }
+
+    /// The stage's layer stack, strongest first, optionally including the session layers.
+    pub fn layer_stack(&self, include_session_layers: bool) -> Vec<sdf::LayerHandle> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetLayerStack(self.ptr, include_session_layers, &mut ptr);
+            sdf::layer_handle_vector_to_vec(ptr)
+        }
+    }
+
+    /// Every layer that contributes to the stage, including those brought in by references
+    /// and payloads.
+    pub fn used_layers(&self) -> Vec<sdf::LayerHandle> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_StageRefPtr_GetUsedLayers(self.ptr, true, &mut ptr);
+            sdf::layer_handle_vector_to_vec(ptr)
+        }
+    }
+
+    /// Save all dirty, non-anonymous layers in the stage's root layer stack.
+    ///
+    /// UsdStage::Save() does not report failure, so we treat any non-anonymous layer
+    /// that is still dirty afterwards as an error. Anonymous layers, such as the root
+    /// layer of a stage created with [`Stage::create_in_memory`], are skipped.
+    pub fn save(&self) -> Result<(), Error> {
+        unsafe {
+            ffi::usd_StageRefPtr_Save(self.ptr);
+        }
+
+        match self
+            .layer_stack(false)
+            .into_iter()
+            .find(|layer| !layer.is_anonymous() && layer.is_dirty())
+        {
+            Some(layer) => Err(Error::StageSave {
+                identifier: layer.identifier().to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Save all dirty, non-anonymous layers in the stage's session layer stack.
+    pub fn save_session_layers(&self) -> Result<(), Error> {
+        unsafe {
+            ffi::usd_StageRefPtr_SaveSessionLayers(self.ptr);
+
+            let session_layer = self.session_layer();
+
+            if !session_layer.is_anonymous() && session_layer.is_dirty() {
+                Err(Error::StageSave {
+                    identifier: session_layer.identifier().to_string(),
+                })
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Flatten the stage's root layer stack and write it to `filename`.
+    pub fn export<P: AsRef<Path>>(&self, filename: P) -> Result<(), Error> {
+        unsafe {
+            let filename = filename.as_ref().to_string_lossy().to_string();
+            let c_filename = CString::new(filename.clone()).unwrap();
+            let mut result = false;
+            ffi::usd_StageRefPtr_Export(self.ptr, c_filename.as_ptr(), true, &mut result);
+
+            if result {
+                Ok(())
+            } else {
+                Err(Error::StageExport { filename })
+            }
+        }
+    }
+}
+
+impl Drop for StageRefPtr {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_StageRefPtr_dtor(self.ptr);
+        }
+    }
+}
+
+unsafe impl Send for StageRefPtr {}
+
+/// Where authoring on a stage is written: a layer, optionally with a mapping into a variant.
+pub struct EditTarget {
+    pub(crate) ptr: *mut ffi::usd_EditTarget_t,
+}
+
+impl EditTarget {
+    pub fn new(layer: &sdf::LayerHandle) -> Self {
+        Self::with_offset(layer, &sdf::LayerOffset::default())
+    }
+
+    pub fn with_offset(layer: &sdf::LayerHandle, offset: &sdf::LayerOffset) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_EditTarget_new(
+                layer.ptr,
+                offset as *const sdf::LayerOffset as *const ffi::sdf_LayerOffset_t,
+                &mut ptr,
+            );
+            EditTarget { ptr }
+        }
+    }
+
+    /// Author into the variant selected by `variant_path`, e.g. `/Model{lod=high}`, in
+    /// `layer`.
+    pub fn for_variant(layer: &sdf::LayerHandle, variant_path: &sdf::Path) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_EditTarget_ForLocalDirectVariant(layer.ptr, variant_path.ptr, &mut ptr);
+            EditTarget { ptr }
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_EditTarget_IsValid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn layer(&self) -> sdf::LayerHandle {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_EditTarget_GetLayer(self.ptr, &mut ptr);
+            sdf::LayerHandle { ptr }
+        }
+    }
+
+    /// The path in the target's layer that edits to the scene path `path` are written to.
+    pub fn map_to_spec_path(&self, path: &sdf::Path) -> sdf::Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_EditTarget_MapToSpecPath(self.ptr, path.ptr, &mut ptr);
+            sdf::Path { ptr }
+        }
+    }
+}
+
+impl Clone for EditTarget {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_EditTarget_copy(self.ptr, &mut ptr);
+            EditTarget { ptr }
+        }
+    }
+}
+
+impl PartialEq for EditTarget {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_EditTarget_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Drop for EditTarget {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_EditTarget_dtor(self.ptr);
+        }
+    }
+}
+
+/// Sets a stage's edit target for the lifetime of the guard, restoring the previous target
+/// when dropped. The equivalent of `UsdEditContext`.
+pub struct EditContext<'a> {
+    stage: &'a StageRefPtr,
+    previous: EditTarget,
+}
+
+impl<'a> EditContext<'a> {
+    pub fn new(stage: &'a StageRefPtr, target: &EditTarget) -> Self {
+        let previous = stage.edit_target();
+        stage.set_edit_target(target);
+        EditContext { stage, previous }
+    }
+}
+
+impl<'a> Drop for EditContext<'a> {
+    fn drop(&mut self) {
+        self.stage.set_edit_target(&self.previous);
+    }
+}
+
+pub struct StageWeakPtr {
+    pub(crate) ptr: *mut ffi::usd_StageWeakPtr_t,
+}
+
+/// A schema class, identified by the name it is registered under, e.g. `"Mesh"`.
+pub trait SchemaBase {
+    const SCHEMA_IDENTIFIER: &'static str;
+}
+
+/// A schema that gives a prim its type. See [`Prim::is_a`].
+pub trait TypedSchema: SchemaBase {}
+
+/// A schema that is applied to prims in addition to their type. See [`Prim::has_api`].
+pub trait ApiSchema: SchemaBase {}
+
+pub trait Object {
+    fn _object_ptr(&self) -> *mut ffi::usd_Object_t;
+
+    fn path(&self) -> sdf::Path {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Object_GetPath(self._object_ptr(), &mut ptr);
+            sdf::Path { ptr }
+        }
+    }
+
+    fn name(&self) -> tf::TokenRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::usd_Object_GetName(self._object_ptr(), &mut ptr);
+            tf::TokenRef::from_ptr(ptr)
+        }
+    }
+
+    fn display_name(&self) -> String {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Object_GetDisplayName(self._object_ptr(), &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            let result = CStr::from_ptr(ptr_c_str).to_string_lossy().to_string();
+            ffi::std_String_dtor(ptr);
+
+            result
+        }
+    }
+
+    /// The composed value of the metadata field `key`, or its fallback if it is not authored.
+    fn get_metadata(&self, key: &str) -> Option<vt::Value> {
+        let key = tf::Token::new(key);
+        unsafe {
+            let value = vt::Value::new();
+            let mut result = false;
+            ffi::usd_Object_GetMetadata(self._object_ptr(), key.ptr, value.ptr, &mut result);
+            if result {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Author the metadata field `key` in the stage's current edit target.
+    fn set_metadata(&self, key: &str, value: &vt::Value) -> Result<(), Error> {
+        let token = tf::Token::new(key);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Object_SetMetadata(self._object_ptr(), token.ptr, value.ptr, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::MetadataSet {
+                path: self.path().text().to_string(),
+                key: key.to_string(),
+            })
+        }
+    }
+
+    fn clear_metadata(&self, key: &str) -> Result<(), Error> {
+        let token = tf::Token::new(key);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Object_ClearMetadata(self._object_ptr(), token.ptr, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::MetadataClear {
+                path: self.path().text().to_string(),
+                key: key.to_string(),
+            })
+        }
+    }
+
+    /// Whether the metadata field `key` has an authored value or a fallback.
+    fn has_metadata(&self, key: &str) -> bool {
+        let key = tf::Token::new(key);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Object_HasMetadata(self._object_ptr(), key.ptr, &mut result);
+            result
+        }
+    }
+
+    fn has_authored_metadata(&self, key: &str) -> bool {
+        let key = tf::Token::new(key);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Object_HasAuthoredMetadata(self._object_ptr(), key.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Every authored metadata field and its composed value.
+    fn all_authored_metadata(&self) -> BTreeMap<tf::Token, vt::Value> {
+        unsafe {
+            let mut map = std::ptr::null_mut();
+            ffi::usd_Object_GetAllAuthoredMetadata(self._object_ptr(), &mut map);
+            let mut size = 0;
+            ffi::usd_MetadataValueMap_size(map, &mut size);
+            let mut result = BTreeMap::new();
+            let mut it = std::ptr::null_mut();
+            ffi::usd_MetadataValueMap_begin(map, &mut it);
+            for _ in 0..size {
+                let mut key = std::ptr::null();
+                ffi::usd_MetadataValueMapIterator_first(it, &mut key);
+                let mut value = std::ptr::null();
+                ffi::usd_MetadataValueMapIterator_second(it, &mut value);
+                let key = tf::TokenRef::from_ptr(key);
+                let value = vt::ValueRef::from_ptr(value);
+                result.insert((*key).clone(), (*value).clone());
+                let mut dummy = std::ptr::null_mut();
+                ffi::usd_MetadataValueMapIterator_op_inc(it, &mut dummy);
+            }
+            ffi::usd_MetadataValueMapIterator_dtor(it);
+            ffi::usd_MetadataValueMap_dtor(map);
+            result
+        }
+    }
+
+    /// The value at `key_path` in the dictionary-valued metadata field `key`. Nested
+    /// dictionaries are addressed with `:`, e.g. `get_metadata_by_dict_key("customData",
+    /// "render:quality")`.
+    fn get_metadata_by_dict_key(&self, key: &str, key_path: &str) -> Option<vt::Value> {
+        let key = tf::Token::new(key);
+        let key_path = tf::Token::new(key_path);
+        unsafe {
+            let value = vt::Value::new();
+            let mut result = false;
+            ffi::usd_Object_GetMetadataByDictKey(
+                self._object_ptr(),
+                key.ptr,
+                key_path.ptr,
+                value.ptr,
+                &mut result,
+            );
+            if result {
+                Some(value)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn set_metadata_by_dict_key(
+        &self,
+        key: &str,
+        key_path: &str,
+        value: &vt::Value,
+    ) -> Result<(), Error> {
+        let key_token = tf::Token::new(key);
+        let key_path_token = tf::Token::new(key_path);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Object_SetMetadataByDictKey(
+                self._object_ptr(),
+                key_token.ptr,
+                key_path_token.ptr,
+                value.ptr,
+                &mut result,
+            );
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::MetadataSet {
+                path: self.path().text().to_string(),
+                key: format!("{key}:{key_path}"),
+            })
+        }
+    }
+
+    fn clear_metadata_by_dict_key(&self, key: &str, key_path: &str) -> Result<(), Error> {
+        let key_token = tf::Token::new(key);
+        let key_path_token = tf::Token::new(key_path);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Object_ClearMetadataByDictKey(
+                self._object_ptr(),
+                key_token.ptr,
+                key_path_token.ptr,
+                &mut result,
+            );
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::MetadataClear {
+                path: self.path().text().to_string(),
+                key: format!("{key}:{key_path}"),
+            })
+        }
+    }
+
+    /// The object's `customData`, or an empty dictionary if none is authored.
+    fn custom_data(&self) -> vt::Dictionary {
+        dictionary_metadata(self, "customData")
+    }
+
+    fn set_custom_data(&self, custom_data: &vt::Dictionary) -> Result<(), Error> {
+        self.set_metadata("customData", &vt::Value::from_member(custom_data))
+    }
+
+    fn set_custom_data_by_key(&self, key_path: &str, value: &vt::Value) -> Result<(), Error> {
+        self.set_metadata_by_dict_key("customData", key_path, value)
+    }
+
+    /// The object's `assetInfo`, or an empty dictionary if none is authored.
+    fn asset_info(&self) -> vt::Dictionary {
+        dictionary_metadata(self, "assetInfo")
+    }
+
+    fn set_asset_info(&self, asset_info: &vt::Dictionary) -> Result<(), Error> {
+        self.set_metadata("assetInfo", &vt::Value::from_member(asset_info))
+    }
+
+    fn set_asset_info_by_key(&self, key_path: &str, value: &vt::Value) -> Result<(), Error> {
+        self.set_metadata_by_dict_key("assetInfo", key_path, value)
+    }
+
+    fn documentation(&self) -> String {
+        self.get_metadata("documentation")
+            .and_then(|value| value.get::<String>().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    fn set_documentation(&self, documentation: &str) -> Result<(), Error> {
+        self.set_metadata(
+            "documentation",
+            &vt::Value::from_member(&documentation.to_string()),
+        )
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.get_metadata("hidden")
+            .and_then(|value| value.get::<bool>().copied())
+            .unwrap_or(false)
+    }
+
+    fn set_hidden(&self, hidden: bool) -> Result<(), Error> {
+        self.set_metadata("hidden", &vt::Value::from_member(&hidden))
+    }
+}
+
+fn dictionary_metadata<O: Object + ?Sized>(object: &O, key: &str) -> vt::Dictionary {
+    object
+        .get_metadata(key)
+        .and_then(|value| value.get::<vt::Dictionary>().map(|dict| (*dict).clone()))
+        .unwrap_or_default()
+}
+
+pub struct Prim {
+    pub(crate) ptr: *mut ffi::usd_Prim_t,
+}
+
+impl Prim {
+    pub fn type_name(&self) -> tf::TokenRef<'_> {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::usd_Prim_GetTypeName(self.ptr, &mut ptr);
+            tf::TokenRef::from_ptr(ptr)
+        }
+    }
+
+    /// Whether this refers to a prim on a stage. Invalid prims are returned in place of
+    /// missing parents, siblings and prototypes.
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsValid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsActive(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Author the prim's `active` metadata in the stage's current edit target.
+    pub fn set_active(&self, active: bool) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Prim_SetActive(self.ptr, active, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::PrimSetActive {
+                path: self.path().text().to_string(),
+            })
+        }
+    }
+
+    /// Whether the prim and all of its ancestors have a defining specifier.
+    pub fn is_defined(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsDefined(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether the prim or one of its ancestors is a class.
+    pub fn is_abstract(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsAbstract(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn specifier(&self) -> Result<sdf::Specifier, Error> {
+        unsafe {
+            let mut result = ffi::sdf_Specifier::sdf_Specifier_SdfSpecifierDef;
+            ffi::usd_Prim_GetSpecifier(self.ptr, &mut result);
+            Ok(result.try_into()?)
+        }
+    }
+
+    /// The parent of this prim, or `None` for the pseudo-root.
+    pub fn parent(&self) -> Option<Prim> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetParent(self.ptr, &mut ptr);
+            Prim { ptr }.valid()
+        }
+    }
+
+    pub fn next_sibling(&self) -> Option<Prim> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetNextSibling(self.ptr, &mut ptr);
+            Prim { ptr }.valid()
+        }
+    }
+
+    pub fn child(&self, name: &str) -> Option<Prim> {
+        let name = tf::Token::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetChild(self.ptr, name.ptr, &mut ptr);
+            Prim { ptr }.valid()
+        }
+    }
+
+    pub fn stage(&self) -> StageWeakPtr {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetStage(self.ptr, &mut ptr);
+            StageWeakPtr { ptr }
+        }
+    }
+
+    /// Whether the prim's type is `S` or derives from it.
+    pub fn is_a<S: TypedSchema>(&self) -> bool {
+        let identifier = tf::Token::new(S::SCHEMA_IDENTIFIER);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsA_with_identifier(self.ptr, identifier.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether the API schema `S` is applied to the prim.
+    pub fn has_api<S: ApiSchema>(&self) -> bool {
+        let identifier = tf::Token::new(S::SCHEMA_IDENTIFIER);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAPI_with_identifier(self.ptr, identifier.ptr, &mut result);
+            result
+        }
+    }
+
+    /// The names of all API schemas applied to the prim, including those from its type.
+    pub fn applied_schemas(&self) -> Vec<tf::Token> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetAppliedSchemas(self.ptr, &mut ptr);
+            let mut size = 0;
+            ffi::tf_TokenVector_size(ptr, &mut size);
+            let mut result = Vec::with_capacity(size);
+            for i in 0..size {
+                let mut token = std::ptr::null();
+                ffi::tf_TokenVector_op_index(ptr, i, &mut token);
+                let token = tf::TokenRef::from_ptr(token);
+                result.push((*token).clone());
+            }
+            ffi::tf_TokenVector_dtor(ptr);
+            result
+        }
+    }
+
+    pub fn is_instance(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsInstance(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether this prim is a descendant of an instance, standing in for a prim in its
+    /// prototype.
+    pub fn is_instance_proxy(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsInstanceProxy(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_in_prototype(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsInPrototype(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// The prototype shared by this instance, or `None` if this is not an instance.
+    pub fn prototype(&self) -> Option<Prim> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetPrototype(self.ptr, &mut ptr);
+            Prim { ptr }.valid()
+        }
+    }
+
+    pub fn is_model(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsModel(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_group(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsGroup(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_component(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsComponent(self.ptr, &mut result);
+            result
+        }
+    }
+
+    fn valid(self) -> Option<Prim> {
+        if self.is_valid() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    pub fn children(&self) -> PrimSiblingRange {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetChildren(self.ptr, &mut ptr);
+            PrimSiblingRange::_from_ptr(ptr)
+        }
+    }
+
+    /// The children of this prim that pass `predicate`.
+    pub fn filtered_children<P: Into<PrimFlagsPredicate>>(&self, predicate: P) -> PrimSiblingRange {
+        let predicate = predicate.into()._to_ffi();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetFilteredChildren(self.ptr, predicate.ptr, &mut ptr);
+            PrimSiblingRange::_from_ptr(ptr)
+        }
+    }
+
+    /// All children of this prim, including inactive, undefined and abstract ones.
+    pub fn all_children(&self) -> PrimSiblingRange {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetAllChildren(self.ptr, &mut ptr);
+            PrimSiblingRange::_from_ptr(ptr)
+        }
+    }
+
+    pub fn properties(&self) -> PropertyVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetProperties(self.ptr, &mut ptr);
+            PropertyVector { ptr }
+        }
+    }
+
+    pub fn create_attribute(
+        &self,
+        name: &str,
+        type_name: &sdf::ValueTypeName,
+        custom: bool,
+        variability: sdf::Variability,
+    ) -> Result<Attribute, Error> {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_CreateAttribute(
+                self.ptr,
+                name_token.ptr,
+                type_name.ptr,
+                custom,
+                variability.into(),
+                &mut ptr,
+            );
+            let mut valid = false;
+            ffi::usd_Attribute_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Attribute { ptr })
+            } else {
+                ffi::usd_Attribute_dtor(ptr);
+                Err(Error::AttributeCreate {
+                    path: self._property_path_text(name),
+                })
+            }
+        }
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Result<Attribute, Error> {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetAttribute(self.ptr, name_token.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Attribute_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Attribute { ptr })
+            } else {
+                ffi::usd_Attribute_dtor(ptr);
+                Err(Error::NoAttributeAtPath {
+                    path: self._property_path_text(name),
+                })
+            }
+        }
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAttribute(self.ptr, name_token.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn create_relationship(&self, name: &str, custom: bool) -> Result<Relationship, Error> {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_CreateRelationship(self.ptr, name_token.ptr, custom, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Relationship_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Relationship { ptr })
+            } else {
+                ffi::usd_Relationship_dtor(ptr);
+                Err(Error::RelationshipCreate {
+                    path: self._property_path_text(name),
+                })
+            }
+        }
+    }
+
+    pub fn get_relationship(&self, name: &str) -> Result<Relationship, Error> {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetRelationship(self.ptr, name_token.ptr, &mut ptr);
+            let mut valid = false;
+            ffi::usd_Relationship_IsValid(ptr, &mut valid);
+
+            if valid {
+                Ok(Relationship { ptr })
+            } else {
+                ffi::usd_Relationship_dtor(ptr);
+                Err(Error::NoRelationshipAtPath {
+                    path: self._property_path_text(name),
+                })
+            }
+        }
+    }
+
+    pub fn has_relationship(&self, name: &str) -> bool {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasRelationship(self.ptr, name_token.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Remove all scene description for the property `name` in the current edit target.
+    pub fn remove_property(&self, name: &str) -> Result<(), Error> {
+        let name_token = tf::Token::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_RemoveProperty(self.ptr, name_token.ptr, &mut result);
+
+            if result {
+                Ok(())
+            } else {
+                Err(Error::PropertyRemove {
+                    path: self._property_path_text(name),
+                })
+            }
+        }
+    }
+
+    pub fn references(&self) -> References {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetReferences(self.ptr, &mut ptr);
+            References {
+                ptr,
+                prim_path: self.path().text().to_string(),
+            }
+        }
+    }
+
+    pub fn has_authored_references(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAuthoredReferences(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn payloads(&self) -> Payloads {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetPayloads(self.ptr, &mut ptr);
+            Payloads {
+                ptr,
+                prim_path: self.path().text().to_string(),
+            }
+        }
+    }
+
+    pub fn has_authored_payloads(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAuthoredPayloads(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn inherits(&self) -> Inherits {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetInherits(self.ptr, &mut ptr);
+            Inherits {
+                ptr,
+                prim_path: self.path().text().to_string(),
+            }
+        }
+    }
+
+    pub fn has_authored_inherits(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAuthoredInherits(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn specializes(&self) -> Specializes {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetSpecializes(self.ptr, &mut ptr);
+            Specializes {
+                ptr,
+                prim_path: self.path().text().to_string(),
+            }
+        }
+    }
+
+    pub fn has_authored_specializes(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasAuthoredSpecializes(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// Whether the prim is loaded. Prims without payloads are loaded unless an ancestor is not.
+    pub fn is_loaded(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_IsLoaded(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn variant_sets(&self) -> VariantSets {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Prim_GetVariantSets(self.ptr, &mut ptr);
+            VariantSets { ptr }
+        }
+    }
+
+    pub fn has_variant_sets(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_HasVariantSets(self.ptr, &mut result);
+            result
+        }
+    }
+
+    fn _property_path_text(&self, name: &str) -> String {
+        format!("{}.{}", self.path().text(), name)
+    }
+}
+
+impl Object for Prim {
+    fn _object_ptr(&self) -> *mut ffi::usd_Object_t {
+        self.ptr as *mut ffi::usd_Object_t
+    }
+}
+
+/// Prims are equal if they refer to the same prim on the same stage.
+impl PartialEq for Prim {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Prim_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl Drop for Prim {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_Prim_dtor(self.ptr);
+        }
+    }
+}
+
+/// Where in a prim's list-edited composition arcs a new arc is added.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ListPosition {
+    FrontOfPrependList,
+    #[default]
+    BackOfPrependList,
+    FrontOfAppendList,
+    BackOfAppendList,
+}
+
+impl From<ListPosition> for ffi::usd_ListPosition {
+    fn from(value: ListPosition) -> Self {
+        match value {
+            ListPosition::FrontOfPrependList => {
+                ffi::usd_ListPosition::usd_ListPosition_UsdListPositionFrontOfPrependList
+            }
+            ListPosition::BackOfPrependList => {
+                ffi::usd_ListPosition::usd_ListPosition_UsdListPositionBackOfPrependList
+            }
+            ListPosition::FrontOfAppendList => {
+                ffi::usd_ListPosition::usd_ListPosition_UsdListPositionFrontOfAppendList
+            }
+            ListPosition::BackOfAppendList => {
+                ffi::usd_ListPosition::usd_ListPosition_UsdListPositionBackOfAppendList
+            }
+        }
+    }
+}
+
+/// Edits the references on a prim in the stage's current edit target.
+pub struct References {
+    pub(crate) ptr: *mut ffi::usd_References_t,
+    prim_path: String,
+}
+
+impl References {
+    /// Reference `prim_path` in the layer at `asset_path`, or its default prim if `prim_path`
+    /// is `None`.
+    pub fn add_reference(
+        &self,
+        asset_path: &str,
+        prim_path: Option<&sdf::Path>,
+        layer_offset: &sdf::LayerOffset,
+        position: ListPosition,
+    ) -> Result<(), Error> {
+        let reference = sdf::Reference::new(asset_path, prim_path, layer_offset);
+        self.add(&reference, position)
+    }
+
+    /// Reference `prim_path` in the stage's own layer stack.
+    pub fn add_internal_reference(
+        &self,
+        prim_path: &sdf::Path,
+        layer_offset: &sdf::LayerOffset,
+        position: ListPosition,
+    ) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_References_AddInternalReference(
+                self.ptr,
+                prim_path.ptr,
+                layer_offset as *const sdf::LayerOffset as *const ffi::sdf_LayerOffset_t,
+                position.into(),
+                &mut result,
+            );
+            self._check(result)
+        }
+    }
+
+    pub fn add(&self, reference: &sdf::Reference, position: ListPosition) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_References_AddReference(self.ptr, reference.ptr, position.into(), &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn remove_reference(&self, reference: &sdf::Reference) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_References_RemoveReference(self.ptr, reference.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn clear_references(&self) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_References_ClearReferences(self.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    /// Explicitly set the references, discarding any list edits.
+    pub fn set_references(&self, references: &[sdf::Reference]) -> Result<(), Error> {
+        let references = sdf::ReferenceVector::new(references);
+        unsafe {
+            let mut result = false;
+            ffi::usd_References_SetReferences(self.ptr, references.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    fn _check(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::CompositionEdit {
+                path: self.prim_path.clone(),
+                arc: "references",
+            })
+        }
+    }
+}
+
+impl Drop for References {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_References_dtor(self.ptr);
+        }
+    }
+}
+
+/// Edits the payloads on a prim in the stage's current edit target.
+pub struct Payloads {
+    pub(crate) ptr: *mut ffi::usd_Payloads_t,
+    prim_path: String,
+}
+
+impl Payloads {
+    /// Add a payload to `prim_path` in the layer at `asset_path`, or its default prim if
+    /// `prim_path` is `None`.
+    pub fn add_payload(
+        &self,
+        asset_path: &str,
+        prim_path: Option<&sdf::Path>,
+        layer_offset: &sdf::LayerOffset,
+        position: ListPosition,
+    ) -> Result<(), Error> {
+        let payload = sdf::Payload::new(asset_path, prim_path, layer_offset);
+        self.add(&payload, position)
+    }
+
+    pub fn add_internal_payload(
+        &self,
+        prim_path: &sdf::Path,
+        layer_offset: &sdf::LayerOffset,
+        position: ListPosition,
+    ) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Payloads_AddInternalPayload(
+                self.ptr,
+                prim_path.ptr,
+                layer_offset as *const sdf::LayerOffset as *const ffi::sdf_LayerOffset_t,
+                position.into(),
+                &mut result,
+            );
+            self._check(result)
+        }
+    }
+
+    pub fn add(&self, payload: &sdf::Payload, position: ListPosition) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Payloads_AddPayload(self.ptr, payload.ptr, position.into(), &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn remove_payload(&self, payload: &sdf::Payload) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Payloads_RemovePayload(self.ptr, payload.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn clear_payloads(&self) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Payloads_ClearPayloads(self.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    /// Explicitly set the payloads, discarding any list edits.
+    pub fn set_payloads(&self, payloads: &[sdf::Payload]) -> Result<(), Error> {
+        let payloads = sdf::PayloadVector::new(payloads);
+        unsafe {
+            let mut result = false;
+            ffi::usd_Payloads_SetPayloads(self.ptr, payloads.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    fn _check(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::CompositionEdit {
+                path: self.prim_path.clone(),
+                arc: "payloads",
+            })
+        }
+    }
+}
+
+impl Drop for Payloads {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_Payloads_dtor(self.ptr);
+        }
+    }
+}
+
+/// Edits the inherit paths on a prim in the stage's current edit target.
+pub struct Inherits {
+    pub(crate) ptr: *mut ffi::usd_Inherits_t,
+    prim_path: String,
+}
+
+impl Inherits {
+    pub fn add_inherit(&self, path: &sdf::Path, position: ListPosition) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Inherits_AddInherit(self.ptr, path.ptr, position.into(), &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn remove_inherit(&self, path: &sdf::Path) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Inherits_RemoveInherit(self.ptr, path.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn clear_inherits(&self) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Inherits_ClearInherits(self.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    /// Explicitly set the inherit paths, discarding any list edits.
+    pub fn set_inherits(&self, paths: &sdf::PathVector) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Inherits_SetInherits(self.ptr, paths.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    /// All inherit paths directly authored on the prim, across all of its specs.
+    pub fn all_direct_inherits(&self) -> sdf::PathVector {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_Inherits_GetAllDirectInherits(self.ptr, &mut ptr);
+            sdf::PathVector { ptr }
+        }
+    }
+
+    fn _check(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::CompositionEdit {
+                path: self.prim_path.clone(),
+                arc: "inherits",
+            })
+        }
+    }
+}
+
+impl Drop for Inherits {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_Inherits_dtor(self.ptr);
+        }
+    }
+}
+
+/// Edits the specializes paths on a prim in the stage's current edit target.
+pub struct Specializes {
+    pub(crate) ptr: *mut ffi::usd_Specializes_t,
+    prim_path: String,
+}
+
+impl Specializes {
+    pub fn add_specialize(&self, path: &sdf::Path, position: ListPosition) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Specializes_AddSpecialize(self.ptr, path.ptr, position.into(), &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn remove_specialize(&self, path: &sdf::Path) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Specializes_RemoveSpecialize(self.ptr, path.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    pub fn clear_specializes(&self) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Specializes_ClearSpecializes(self.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    /// Explicitly set the specializes paths, discarding any list edits.
+    pub fn set_specializes(&self, paths: &sdf::PathVector) -> Result<(), Error> {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Specializes_SetSpecializes(self.ptr, paths.ptr, &mut result);
+            self._check(result)
+        }
+    }
+
+    fn _check(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::CompositionEdit {
+                path: self.prim_path.clone(),
+                arc: "specializes",
+            })
+        }
+    }
+}
+
+impl Drop for Specializes {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_Specializes_dtor(self.ptr);
+        }
+    }
+}
+
+/// The variant sets on a prim.
+pub struct VariantSets {
+    pub(crate) ptr: *mut ffi::usd_VariantSets_t,
+}
+
+impl VariantSets {
+    pub fn add_variant_set(&self, name: &str, position: ListPosition) -> Result<VariantSet, Error> {
+        let name_str = cpp::String::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSets_AddVariantSet(self.ptr, name_str.ptr, position.into(), &mut ptr);
+            let variant_set = VariantSet { ptr };
+
+            if variant_set.is_valid() {
+                Ok(variant_set)
+            } else {
+                Err(Error::VariantSetCreate {
+                    path: variant_set.prim().path().text().to_string(),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+
+    /// The names of all variant sets authored on the prim.
+    pub fn names(&self) -> Vec<String> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSets_GetNames(self.ptr, &mut ptr);
+            cpp::StringVector::from_ptr(ptr).to_vec()
+        }
+    }
+
+    pub fn has_variant_set(&self, name: &str) -> bool {
+        let name = cpp::String::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::usd_VariantSets_HasVariantSet(self.ptr, name.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<VariantSet, Error> {
+        let name_str = cpp::String::new(name);
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSets_GetVariantSet(self.ptr, name_str.ptr, &mut ptr);
+            let variant_set = VariantSet { ptr };
+
+            if self.has_variant_set(name) {
+                Ok(variant_set)
+            } else {
+                Err(Error::NoVariantSet {
+                    path: variant_set.prim().path().text().to_string(),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl Drop for VariantSets {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_VariantSets_dtor(self.ptr);
+        }
+    }
+}
+
+/// A single named variant set on a prim.
+pub struct VariantSet {
+    pub(crate) ptr: *mut ffi::usd_VariantSet_t,
+}
+
+impl VariantSet {
+    pub fn is_valid(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_IsValid(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn name(&self) -> String {
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::usd_VariantSet_GetName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_string_lossy().to_string()
+        }
+    }
+
+    pub fn prim(&self) -> Prim {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSet_GetPrim(self.ptr, &mut ptr);
+            Prim { ptr }
+        }
+    }
+
+    pub fn variant_names(&self) -> Vec<String> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSet_GetVariantNames(self.ptr, &mut ptr);
+            cpp::StringVector::from_ptr(ptr).to_vec()
+        }
+    }
+
+    pub fn has_authored_variant(&self, name: &str) -> bool {
+        let name = cpp::String::new(name);
+        unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_HasAuthoredVariant(self.ptr, name.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn add_variant(&self, name: &str, position: ListPosition) -> Result<(), Error> {
+        let name = cpp::String::new(name);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_AddVariant(self.ptr, name.ptr, position.into(), &mut result);
+            result
+        };
+        self._check(result)
+    }
+
+    /// The composed variant selection, or an empty string if there is none.
+    pub fn variant_selection(&self) -> String {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSet_GetVariantSelection(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            let result = CStr::from_ptr(ptr_c_str).to_string_lossy().to_string();
+            ffi::std_String_dtor(ptr);
+
+            result
+        }
+    }
+
+    pub fn has_authored_variant_selection(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_HasAuthoredVariantSelection(
+                self.ptr,
+                std::ptr::null_mut(),
+                &mut result,
+            );
+            result
+        }
+    }
+
+    pub fn set_variant_selection(&self, name: &str) -> Result<(), Error> {
+        let name = cpp::String::new(name);
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_SetVariantSelection(self.ptr, name.ptr, &mut result);
+            result
+        };
+        self._check(result)
+    }
+
+    pub fn clear_variant_selection(&self) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_VariantSet_ClearVariantSelection(self.ptr, &mut result);
+            result
+        };
+        self._check(result)
+    }
+
+    /// An edit target that authors into the currently selected variant, in `layer`.
+    pub fn variant_edit_target(&self, layer: &sdf::LayerHandle) -> EditTarget {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_VariantSet_GetVariantEditTarget(self.ptr, layer.ptr, &mut ptr);
+            EditTarget { ptr }
+        }
+    }
+
+    /// Direct authoring on `stage` into the currently selected variant, in the layer of the
+    /// stage's current edit target, until the returned guard is dropped.
+    ///
+    /// `stage` must be the stage that owns the variant set's prim.
+    pub fn variant_edit_context<'a>(
+        &self,
+        stage: &'a StageRefPtr,
+    ) -> Result<EditContext<'a>, Error> {
+        let prim = self.prim();
+        let path = prim.path();
+        match stage.prim_at_path(path.clone()) {
+            Ok(p) if p == prim => {
+                let target = self.variant_edit_target(&stage.edit_target().layer());
+                Ok(EditContext::new(stage, &target))
+            }
+            _ => Err(Error::PrimNotOnStage {
+                path: path.text().to_string(),
+            }),
+        }
+    }
+
+    fn _check(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::VariantSetEdit {
+                path: self.prim().path().text().to_string(),
+                name: self.name(),
+            })
+        }
+    }
+}
+
+impl Drop for VariantSet {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::usd_VariantSet_dtor(self.ptr);
+        }
+    }
+}
+
+/// A prim state that can be tested for in a [`PrimFlagsPredicate`].
+///
+/// Flags and their negations combine with `&` and `|`, e.g.
+/// `PrimFlag::Active & PrimFlag::Defined & !PrimFlag::Abstract`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimFlag {
+    Active,
+    Loaded,
+    Model,
+    Group,
+    Abstract,
+    Defined,
+    HasDefiningSpecifier,
+    Instance,
+}
+
+impl PrimFlag {
+    const ALL: [PrimFlag; 8] = [
+        PrimFlag::Active,
+        PrimFlag::Loaded,
+        PrimFlag::Model,
+        PrimFlag::Group,
+        PrimFlag::Abstract,
+        PrimFlag::Defined,
+        PrimFlag::HasDefiningSpecifier,
+        PrimFlag::Instance,
+    ];
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+impl From<PrimFlag> for ffi::usd_PrimFlags {
+    fn from(value: PrimFlag) -> Self {
+        match value {
+            PrimFlag::Active => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimActiveFlag,
+            PrimFlag::Loaded => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimLoadedFlag,
+            PrimFlag::Model => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimModelFlag,
+            PrimFlag::Group => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimGroupFlag,
+            PrimFlag::Abstract => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimAbstractFlag,
+            PrimFlag::Defined => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimDefinedFlag,
+            PrimFlag::HasDefiningSpecifier => {
+                ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimHasDefiningSpecifierFlag
+            }
+            PrimFlag::Instance => ffi::usd_PrimFlags::usd_PrimFlags_Usd_PrimInstanceFlag,
         }
     }
 }
 
-#[derive(Debug)]
-pub struct StageRefPtr {
-    pub(crate) ptr: *mut ffi::usd_StageRefPtr_t,
+/// A [`PrimFlag`], or its negation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimFlagsTerm {
+    flag: PrimFlag,
+    negated: bool,
 }
 
-impl StageRefPtr {
-    pub fn pseudo_root(&self) -> Prim {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_StageRefPtr_GetPseudoRoot(self.ptr, &mut ptr);
-            Prim { ptr }
+impl From<PrimFlag> for PrimFlagsTerm {
+    fn from(flag: PrimFlag) -> Self {
+        PrimFlagsTerm {
+            flag,
+            negated: false,
         }
     }
+}
 
-    pub fn prim_at_path<P: Into<sdf::Path>>(&self, path: P) -> Result<Prim, Error> {
-        let path = path.into();
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_StageRefPtr_GetPrimAtPath(self.ptr, path.ptr, &mut ptr);
-            let mut valid = false;
-            ffi::usd_Prim_IsValid(ptr, &mut valid);
+impl std::ops::Not for PrimFlag {
+    type Output = PrimFlagsTerm;
 
-            if valid {
-                Ok(Prim{ptr})
-            } else {
-                Err(Error::NoPrimAtPath { path: path.text().to_string() })
-            }
+    fn not(self) -> Self::Output {
+        PrimFlagsTerm {
+            flag: self,
+            negated: true,
         }
     }
+}
 
-    pub fn as_weak(&self) -> StageWeakPtr {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_StageRefPtr_as_weak(self.ptr, &mut ptr);
-            StageWeakPtr { ptr }
+impl std::ops::Not for PrimFlagsTerm {
+    type Output = PrimFlagsTerm;
+
+    fn not(self) -> Self::Output {
+        PrimFlagsTerm {
+            flag: self.flag,
+            negated: !self.negated,
         }
     }
 }
 
-impl Drop for StageRefPtr {
-    fn drop(&mut self) {
-        unsafe {
-            ffi::usd_StageRefPtr_dtor(self.ptr);
+/// Terms that must all hold, built with `&`.
+///
+/// A conjunction of a flag and its negation, e.g. `Active & !Active`, matches no prims.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimFlagsConjunction {
+    mask: u32,
+    values: u32,
+    contradiction: bool,
+}
+
+impl PrimFlagsConjunction {
+    /// The empty conjunction, which matches every prim.
+    const TAUTOLOGY: PrimFlagsConjunction = PrimFlagsConjunction {
+        mask: 0,
+        values: 0,
+        contradiction: false,
+    };
+
+    /// The conjunction that matches no prims. Every contradictory conjunction is normalized
+    /// to this so that they compare equal.
+    const CONTRADICTION: PrimFlagsConjunction = PrimFlagsConjunction {
+        mask: 0,
+        values: 0,
+        contradiction: true,
+    };
+
+    fn with_term(mut self, term: PrimFlagsTerm) -> Self {
+        if self.contradiction {
+            return self;
+        }
+
+        let bit = term.flag.bit();
+        let value = if term.negated { 0 } else { bit };
+        if self.mask & bit != 0 && self.values & bit != value {
+            return Self::CONTRADICTION;
         }
+
+        self.mask |= bit;
+        self.values |= value;
+        self
     }
 }
 
-unsafe impl Send for StageRefPtr {}
+/// Terms of which at least one must hold, built with `|`.
+///
+/// A disjunction of a flag and its negation, e.g. `Active | !Active`, matches every prim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimFlagsDisjunction {
+    // Stored as the conjunction of the negated terms, which this is the negation of.
+    negated: PrimFlagsConjunction,
+}
 
-pub struct StageWeakPtr {
-    pub(crate) ptr: *mut ffi::usd_StageWeakPtr_t,
+macro_rules! prim_flags_ops {
+    ($($lhs:ty),*) => {
+        $(
+            impl From<$lhs> for PrimFlagsConjunction {
+                fn from(term: $lhs) -> Self {
+                    PrimFlagsConjunction::TAUTOLOGY.with_term(term.into())
+                }
+            }
+
+            impl From<$lhs> for PrimFlagsDisjunction {
+                fn from(term: $lhs) -> Self {
+                    PrimFlagsDisjunction {
+                        negated: PrimFlagsConjunction::from(!PrimFlagsTerm::from(term)),
+                    }
+                }
+            }
+
+            impl From<$lhs> for PrimFlagsPredicate {
+                fn from(term: $lhs) -> Self {
+                    PrimFlagsConjunction::from(term).into()
+                }
+            }
+
+            impl<T: Into<PrimFlagsTerm>> std::ops::BitAnd<T> for $lhs {
+                type Output = PrimFlagsConjunction;
+
+                fn bitand(self, rhs: T) -> Self::Output {
+                    PrimFlagsConjunction::from(self).with_term(rhs.into())
+                }
+            }
+
+            impl<T: Into<PrimFlagsTerm>> std::ops::BitOr<T> for $lhs {
+                type Output = PrimFlagsDisjunction;
+
+                fn bitor(self, rhs: T) -> Self::Output {
+                    let lhs = PrimFlagsDisjunction::from(self);
+                    PrimFlagsDisjunction {
+                        negated: lhs.negated.with_term(!rhs.into()),
+                    }
+                }
+            }
+        )*
+    };
 }
 
-pub trait Object {
-    fn _object_ptr(&self) -> *mut ffi::usd_Object_t;
+prim_flags_ops!(PrimFlag, PrimFlagsTerm);
 
-    fn path(&self) -> sdf::Path {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_Object_GetPath(self._object_ptr(), &mut ptr);
-            sdf::Path { ptr }
-        }
+impl<T: Into<PrimFlagsTerm>> std::ops::BitAnd<T> for PrimFlagsConjunction {
+    type Output = PrimFlagsConjunction;
+
+    fn bitand(self, rhs: T) -> Self::Output {
+        self.with_term(rhs.into())
     }
+}
 
-    fn name(&self) -> tf::TokenRef {
-        unsafe {
-            let mut ptr = std::ptr::null();
-            ffi::usd_Object_GetName(self._object_ptr(), &mut ptr);
-            tf::TokenRef { ptr }
+impl<T: Into<PrimFlagsTerm>> std::ops::BitOr<T> for PrimFlagsDisjunction {
+    type Output = PrimFlagsDisjunction;
+
+    fn bitor(self, rhs: T) -> Self::Output {
+        PrimFlagsDisjunction {
+            negated: self.negated.with_term(!rhs.into()),
         }
     }
+}
 
-    fn display_name(&self) -> String {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_Object_GetDisplayName(self._object_ptr(), &mut ptr);
-            let mut ptr_c_str = std::ptr::null();
-            ffi::std_String_c_str(ptr, &mut ptr_c_str);
-            let result = CStr::from_ptr(ptr_c_str).to_string_lossy().to_string();
-            ffi::std_String_dtor(ptr);
+impl std::ops::Not for PrimFlagsConjunction {
+    type Output = PrimFlagsDisjunction;
 
-            result
-        }
+    fn not(self) -> Self::Output {
+        PrimFlagsDisjunction { negated: self }
     }
 }
 
-pub struct Prim {
-    pub(crate) ptr: *mut ffi::usd_Prim_t,
+impl std::ops::Not for PrimFlagsDisjunction {
+    type Output = PrimFlagsConjunction;
+
+    fn not(self) -> Self::Output {
+        self.negated
+    }
 }
 
-impl Prim {
-    pub fn type_name(&self) -> tf::TokenRef {
-        unsafe {
-            let mut ptr = std::ptr::null();
-            ffi::usd_Prim_GetTypeName(self.ptr, &mut ptr);
-            tf::TokenRef { ptr }
+/// A filter on prim flags used to select which prims are traversed.
+///
+/// Built from a [`PrimFlag`], [`PrimFlagsTerm`], [`PrimFlagsConjunction`] or
+/// [`PrimFlagsDisjunction`]. The default predicate matches `Active & Loaded & Defined &
+/// !Abstract`, as `UsdPrimDefaultPredicate` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimFlagsPredicate {
+    conjunction: PrimFlagsConjunction,
+    negate: bool,
+    traverse_instance_proxies: bool,
+}
+
+impl PrimFlagsPredicate {
+    /// A predicate that matches every prim.
+    pub fn all() -> Self {
+        PrimFlagsPredicate {
+            conjunction: PrimFlagsConjunction::TAUTOLOGY,
+            negate: false,
+            traverse_instance_proxies: false,
         }
     }
 
-    pub fn children(&self) -> PrimSiblingRange {
+    /// Also descend into instances, yielding their instance proxies.
+    pub fn traverse_instance_proxies(mut self, traverse: bool) -> Self {
+        self.traverse_instance_proxies = traverse;
+        self
+    }
+
+    fn _to_ffi(self) -> PrimFlagsPredicateHandle {
         unsafe {
+            let mut conjunction = std::ptr::null_mut();
+            ffi::usd_PrimFlagsConjunction_default(&mut conjunction);
+            for flag in PrimFlag::ALL {
+                if self.conjunction.mask & flag.bit() != 0 {
+                    let negated = self.conjunction.values & flag.bit() == 0;
+                    let mut term = std::ptr::null_mut();
+                    ffi::usd_Term_new(flag.into(), negated, &mut term);
+                    let mut dummy = std::ptr::null_mut();
+                    ffi::usd_PrimFlagsConjunction_op_and_assign(conjunction, term, &mut dummy);
+                    ffi::usd_Term_dtor(term);
+                }
+            }
+
             let mut ptr = std::ptr::null_mut();
-            ffi::usd_Prim_GetChildren(self.ptr, &mut ptr);
-            PrimSiblingRange::_from_ptr(ptr)
+            ffi::usd_PrimFlagsConjunction_as_predicate(conjunction, &mut ptr);
+            ffi::usd_PrimFlagsConjunction_dtor(conjunction);
+
+            if self.negate {
+                let mut negated = std::ptr::null_mut();
+                ffi::usd_PrimFlagsPredicate_op_not(ptr, &mut negated);
+                ffi::usd_PrimFlagsPredicate_dtor(ptr);
+                ptr = negated;
+            }
+
+            if self.traverse_instance_proxies {
+                let mut with_proxies = std::ptr::null_mut();
+                ffi::usd_TraverseInstanceProxies(ptr, &mut with_proxies);
+                ffi::usd_PrimFlagsPredicate_dtor(ptr);
+                ptr = with_proxies;
+            }
+
+            PrimFlagsPredicateHandle { ptr }
         }
     }
+}
 
-    pub fn properties(&self) -> PropertyVector {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::usd_Prim_GetProperties(self.ptr, &mut ptr);
-            PropertyVector { ptr }
+impl Default for PrimFlagsPredicate {
+    fn default() -> Self {
+        (PrimFlag::Active & PrimFlag::Loaded & PrimFlag::Defined & !PrimFlag::Abstract).into()
+    }
+}
+
+impl PrimFlagsPredicate {
+    /// USD's conjunctions can't express a contradiction, so one is built as the negation of
+    /// the empty conjunction instead.
+    fn _from_conjunction(conjunction: PrimFlagsConjunction, negate: bool) -> Self {
+        if conjunction.contradiction {
+            PrimFlagsPredicate {
+                conjunction: PrimFlagsConjunction::TAUTOLOGY,
+                negate: !negate,
+                traverse_instance_proxies: false,
+            }
+        } else {
+            PrimFlagsPredicate {
+                conjunction,
+                negate,
+                traverse_instance_proxies: false,
+            }
         }
     }
 }
 
-impl Object for Prim {
-    fn _object_ptr(&self) -> *mut ffi::usd_Object_t {
-        self.ptr as *mut ffi::usd_Object_t
+impl From<PrimFlagsConjunction> for PrimFlagsPredicate {
+    fn from(conjunction: PrimFlagsConjunction) -> Self {
+        PrimFlagsPredicate::_from_conjunction(conjunction, false)
     }
 }
 
-impl Drop for Prim {
+impl From<PrimFlagsDisjunction> for PrimFlagsPredicate {
+    fn from(disjunction: PrimFlagsDisjunction) -> Self {
+        PrimFlagsPredicate::_from_conjunction(disjunction.negated, true)
+    }
+}
+
+/// Owns the C++ predicate built from a [`PrimFlagsPredicate`] for the duration of a call.
+struct PrimFlagsPredicateHandle {
+    ptr: *mut ffi::usd_PrimFlagsPredicate_t,
+}
+
+impl Drop for PrimFlagsPredicateHandle {
     fn drop(&mut self) {
         unsafe {
-            ffi::usd_Prim_dtor(self.ptr);
+            ffi::usd_PrimFlagsPredicate_dtor(self.ptr);
         }
     }
 }
@@ -186,6 +2830,9 @@ pub struct PrimRange {
     ptr: *mut ffi::usd_PrimRange_t,
     current: PrimRangeIterator,
     end: PrimRangeIterator,
+    // The iterator is left on the last prim returned until the next call to `next()`, so that
+    // its children can still be pruned.
+    pending_increment: bool,
 }
 
 impl PrimRange {
@@ -201,6 +2848,7 @@ impl PrimRange {
                 ptr,
                 current: PrimRangeIterator { ptr: current },
                 end: PrimRangeIterator { ptr: end },
+                pending_increment: false,
             }
         }
     }
@@ -213,6 +2861,64 @@ impl PrimRange {
         }
     }
 
+    /// Iterate over `prim` and its descendants that pass `predicate`.
+    pub fn with_predicate<P: Into<PrimFlagsPredicate>>(prim: &Prim, predicate: P) -> Self {
+        let predicate = predicate.into()._to_ffi();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_PrimRange_from_prim_with_predicate(prim.ptr, predicate.ptr, &mut ptr);
+            PrimRange::_from_ptr(ptr)
+        }
+    }
+
+    /// Iterate over the prims on `stage` that pass `predicate`, starting from the pseudo-root.
+    /// The equivalent of `UsdPrimRange::Stage`.
+    pub fn stage_traverse<P: Into<PrimFlagsPredicate>>(stage: &StageRefPtr, predicate: P) -> Self {
+        let predicate = predicate.into()._to_ffi();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_PrimRange_Stage(stage.ptr, predicate.ptr, &mut ptr);
+            PrimRange::_from_ptr(ptr)
+        }
+    }
+
+    /// Iterate over `prim` and its descendants, visiting each prim both before and after its
+    /// descendants.
+    pub fn pre_and_post_visit(prim: &Prim) -> PrimPreAndPostRange {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_PrimRange_PreAndPostVisit(prim.ptr, &mut ptr);
+            PrimPreAndPostRange {
+                range: PrimRange::_from_ptr(ptr),
+            }
+        }
+    }
+
+    /// Iterate over `prim` and its descendants that pass `predicate`, visiting each prim both
+    /// before and after its descendants.
+    pub fn pre_and_post_visit_with_predicate<P: Into<PrimFlagsPredicate>>(
+        prim: &Prim,
+        predicate: P,
+    ) -> PrimPreAndPostRange {
+        let predicate = predicate.into()._to_ffi();
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::usd_PrimRange_PreAndPostVisit_with_predicate(prim.ptr, predicate.ptr, &mut ptr);
+            PrimPreAndPostRange {
+                range: PrimRange::_from_ptr(ptr),
+            }
+        }
+    }
+
+    /// Skip the descendants of the prim most recently returned by `next()`.
+    pub fn prune_children(&mut self) {
+        if self.pending_increment {
+            unsafe {
+                ffi::usd_PrimRangeIterator_PruneChildren(self.current.ptr);
+            }
+        }
+    }
+
     pub fn begin(&self) -> PrimRangeIterator {
         unsafe {
             let mut ptr = std::ptr::null_mut();
@@ -234,17 +2940,63 @@ impl Iterator for PrimRange {
     type Item = Prim;
 
     fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            if self.pending_increment {
+                let mut dummy = std::ptr::null_mut();
+                ffi::usd_PrimRangeIterator_op_inc(self.current.ptr, &mut dummy);
+                self.pending_increment = false;
+            }
+        }
+
         if self.current == self.end {
             None
         } else {
-            unsafe {
-                let prim = self.current.deref();
+            self.pending_increment = true;
+            Some(self.current.deref())
+        }
+    }
+}
 
-                let mut dummy = std::ptr::null_mut();
-                ffi::usd_PrimRangeIterator_op_inc(self.current.ptr, &mut dummy);
+/// A prim visited by [`PrimPreAndPostRange`], before or after its descendants.
+pub enum Visit {
+    Pre(Prim),
+    Post(Prim),
+}
 
-                Some(prim)
-            }
+impl Visit {
+    pub fn prim(&self) -> &Prim {
+        match self {
+            Visit::Pre(prim) | Visit::Post(prim) => prim,
+        }
+    }
+}
+
+/// Iterates over a prim subtree in depth-first order, yielding every prim once before and
+/// once after its descendants.
+pub struct PrimPreAndPostRange {
+    range: PrimRange,
+}
+
+impl PrimPreAndPostRange {
+    /// Skip the descendants of the prim most recently returned by `next()`, so that its
+    /// [`Visit::Post`] comes next. Does nothing after a [`Visit::Post`], whose descendants
+    /// have already been visited.
+    pub fn prune_children(&mut self) {
+        if self.range.pending_increment && !self.range.current.is_post_visit() {
+            self.range.prune_children();
+        }
+    }
+}
+
+impl Iterator for PrimPreAndPostRange {
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prim = self.range.next()?;
+        if self.range.current.is_post_visit() {
+            Some(Visit::Post(prim))
+        } else {
+            Some(Visit::Pre(prim))
         }
     }
 }
@@ -269,6 +3021,14 @@ impl PrimRangeIterator {
             Prim { ptr }
         }
     }
+
+    fn is_post_visit(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_PrimRangeIterator_IsPostVisit(self.ptr, &mut result);
+            result
+        }
+    }
 }
 
 impl Drop for PrimRangeIterator {
@@ -578,43 +3338,207 @@ impl<'a> IntoIterator for &'a PropertyVector {
 }
 
 
-pub struct Attribute {
-    pub(crate) ptr: *mut ffi::usd_Attribute_t,
-}
+pub struct Attribute {
+    pub(crate) ptr: *mut ffi::usd_Attribute_t,
+}
+
+impl Attribute {
+    pub fn get(&self) -> Option<vt::Value> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Value_new(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get(self.ptr, ptr, TimeCode::default().0, &mut result);
+            if result {
+                Some(vt::Value { ptr })
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn get_at(&self, time: TimeCode) -> Option<vt::Value> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Value_new(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get(self.ptr, ptr, time.0, &mut result);
+            if result {
+                Some(vt::Value { ptr })
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn set(&self, value: &vt::Value) -> Result<(), Error> {
+        self.set_at(value, TimeCode::default())
+    }
+
+    pub fn set_at(&self, value: &vt::Value, time: TimeCode) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_Set(self.ptr, value.ptr, time.0, &mut result);
+            result
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(Error::AttributeSet {
+                path: self.path().text().to_string(),
+            })
+        }
+    }
+
+    /// Read the attribute's value at `time` as a `T` without going through a `vt::Value`.
+    ///
+    /// Returns [`Error::TypeMismatch`] if the attribute has a value that is not a `T`, and
+    /// [`Error::NoValue`] if it has no authored or fallback value at all.
+    pub fn get_typed<T: vt::ValueMember>(&self, time: TimeCode) -> Result<T, Error> {
+        if let Some(value) = T::attribute_get(self, time) {
+            return Ok(value);
+        }
+
+        if self.has_value() {
+            Err(self._type_mismatch::<T>())
+        } else {
+            Err(Error::NoValue {
+                path: self.path().text().to_string(),
+            })
+        }
+    }
+
+    /// Write `value` at `time` through the typed `UsdAttribute::Set<T>` overload.
+    ///
+    /// Returns [`Error::TypeMismatch`] if `T` is not the attribute's value type, and
+    /// [`Error::AttributeSet`] if authoring fails for any other reason, such as an invalid
+    /// attribute or edit target.
+    pub fn set_typed<T: vt::ValueMember>(&self, value: &T, time: TimeCode) -> Result<(), Error> {
+        let type_name = self.type_name();
+        if type_name.is_valid() && !type_name.holds_type::<T>() {
+            return Err(self._type_mismatch::<T>());
+        }
+
+        if T::attribute_set(self, value, time) {
+            Ok(())
+        } else {
+            Err(Error::AttributeSet {
+                path: self.path().text().to_string(),
+            })
+        }
+    }
+
+    pub fn has_value(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_HasValue(self.ptr, &mut result);
+            result
+        }
+    }
+
+    fn _type_mismatch<T>(&self) -> Error {
+        Error::TypeMismatch {
+            path: self.path().text().to_string(),
+            type_name: self.type_name().as_token().text().to_string(),
+            requested: std::any::type_name::<T>(),
+        }
+    }
 
-impl Attribute {
-    pub fn get(&self) -> Option<vt::Value> {
+    pub fn time_samples(&self) -> Vec<f64> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_new(&mut ptr);
+            ffi::std_DoubleVector_default(&mut ptr);
             let mut result = false;
-            ffi::usd_Attribute_Get(self.ptr, ptr, TimeCode::default().0, &mut result);
-            if result {
-                Some(vt::Value { ptr })
-            } else {
-                None
-            }
+            ffi::usd_Attribute_GetTimeSamples(self.ptr, ptr, &mut result);
+            let times = if result { double_vector_to_vec(ptr) } else { Vec::new() };
+            ffi::std_DoubleVector_dtor(ptr);
+            times
         }
     }
 
-    pub fn get_at(&self, time: TimeCode) -> Option<vt::Value> {
+    /// Time samples in the closed interval `[start, end]`.
+    pub fn time_samples_in_interval(&self, start: f64, end: f64) -> Vec<f64> {
         unsafe {
+            let mut interval = std::ptr::null_mut();
+            ffi::gf_Interval_from_min_max(start, end, &mut interval);
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_new(&mut ptr);
+            ffi::std_DoubleVector_default(&mut ptr);
             let mut result = false;
-            ffi::usd_Attribute_Get(self.ptr, ptr, time.0, &mut result);
-            if result {
-                Some(vt::Value { ptr })
+            ffi::usd_Attribute_GetTimeSamplesInInterval(self.ptr, interval, ptr, &mut result);
+            let times = if result { double_vector_to_vec(ptr) } else { Vec::new() };
+            ffi::std_DoubleVector_dtor(ptr);
+            ffi::gf_Interval_dtor(interval);
+            times
+        }
+    }
+
+    pub fn num_time_samples(&self) -> usize {
+        unsafe {
+            let mut result = 0;
+            ffi::usd_Attribute_GetNumTimeSamples(self.ptr, &mut result);
+            result
+        }
+    }
+
+    /// The authored time samples on either side of `time`, or `None` if the
+    /// attribute has no time samples.
+    pub fn bracketing_time_samples(&self, time: f64) -> Option<(f64, f64)> {
+        unsafe {
+            let mut lower = 0.0;
+            let mut upper = 0.0;
+            let mut has_time_samples = false;
+            let mut result = false;
+            ffi::usd_Attribute_GetBracketingTimeSamples(
+                self.ptr,
+                time,
+                &mut lower,
+                &mut upper,
+                &mut has_time_samples,
+                &mut result,
+            );
+
+            if result && has_time_samples {
+                Some((lower, upper))
             } else {
                 None
             }
         }
     }
 
-    pub fn set(&self, value: &vt::Value) {
+    pub fn value_might_be_time_varying(&self) -> bool {
         unsafe {
             let mut result = false;
-            ffi::usd_Attribute_Set(self.ptr, value.ptr, TimeCode::default().0, &mut result);
+            ffi::usd_Attribute_ValueMightBeTimeVarying(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn clear_at(&self, time: TimeCode) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_ClearAtTime(self.ptr, time.0, &mut result);
+            result
+        };
+        self._clear_result(result)
+    }
+
+    /// Clear the default value and all time samples in the current edit target.
+    pub fn clear(&self) -> Result<(), Error> {
+        let result = unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_Clear(self.ptr, &mut result);
+            result
+        };
+        self._clear_result(result)
+    }
+
+    fn _clear_result(&self, result: bool) -> Result<(), Error> {
+        if result {
+            Ok(())
+        } else {
+            Err(Error::AttributeClear {
+                path: self.path().text().to_string(),
+            })
         }
     }
 
@@ -732,7 +3656,58 @@ pub enum PropertyKind {
 }
 
 #[repr(transparent)]
-pub struct TimeCode(ffi::usd_TimeCode_t);
+#[derive(Clone, Copy)]
+pub struct TimeCode(pub(crate) ffi::usd_TimeCode_t);
+
+impl TimeCode {
+    pub fn new(time: f64) -> Self {
+        TimeCode(ffi::usd_TimeCode_t { time })
+    }
+
+    pub fn earliest_time() -> Self {
+        unsafe {
+            let mut tc = ffi::usd_TimeCode_t { time: 0.0 };
+            ffi::usd_TimeCode_EarliestTime(&mut tc);
+            TimeCode(tc)
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0.time
+    }
+
+    pub fn is_default(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_TimeCode_IsDefault(&self.0, &mut result);
+            result
+        }
+    }
+
+    pub fn is_earliest_time(&self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_TimeCode_IsEarliestTime(&self.0, &mut result);
+            result
+        }
+    }
+}
+
+impl From<f64> for TimeCode {
+    fn from(time: f64) -> Self {
+        TimeCode::new(time)
+    }
+}
+
+impl fmt::Debug for TimeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_default() {
+            write!(f, "TimeCode(DEFAULT)")
+        } else {
+            write!(f, "TimeCode({})", self.0.time)
+        }
+    }
+}
 
 impl Default for TimeCode {
     fn default() -> Self {
@@ -743,3 +3718,890 @@ impl Default for TimeCode {
         }
     }
 }
+
+unsafe fn double_vector_to_vec(ptr: *mut ffi::std_DoubleVector_t) -> Vec<f64> {
+    let mut size = 0;
+    ffi::std_DoubleVector_size(ptr, &mut size);
+    if size == 0 {
+        return Vec::new();
+    }
+    let mut data = std::ptr::null();
+    ffi::std_DoubleVector_data(ptr, &mut data);
+    std::slice::from_raw_parts(data, size).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_path;
+
+    #[test]
+    fn create_new_save_and_reopen() {
+        let path = temp_path("create_new.usda");
+        let stage = Stage::create_new(&path, InitialLoadSet::LoadAll).unwrap();
+        crate::usd_geom::Mesh::define(&stage.as_weak(), &sdf::Path::new("/Mesh").unwrap());
+        stage.save().unwrap();
+        drop(stage);
+
+        let stage = Stage::open(&path, InitialLoadSet::LoadAll).unwrap();
+        assert!(stage.prim_at_path("/Mesh").is_ok());
+    }
+
+    #[test]
+    fn export_in_memory_stage() {
+        let path = temp_path("export.usda");
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        crate::usd_geom::Mesh::define(&stage.as_weak(), &sdf::Path::new("/Mesh").unwrap());
+        stage.export(&path).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("#usda"));
+        assert!(text.contains("def Mesh \"Mesh\""));
+    }
+
+    #[test]
+    fn save_in_memory_stage_skips_anonymous_root() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        crate::usd_geom::Mesh::define(&stage.as_weak(), &sdf::Path::new("/Mesh").unwrap());
+        assert!(stage.root_layer().is_dirty());
+        stage.save().unwrap();
+    }
+
+    #[test]
+    fn open_missing_file_is_an_error() {
+        let path = temp_path("missing.usda");
+        assert!(matches!(
+            Stage::open(&path, InitialLoadSet::LoadAll),
+            Err(Error::StageOpen { .. })
+        ));
+    }
+
+    #[test]
+    fn define_override_and_class_prims() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+
+        let world = stage.define_prim("/World", "Xform").unwrap();
+        assert_eq!(world.type_name().text(), "Xform");
+        assert_eq!(world.path().text(), "/World");
+
+        let over = stage.override_prim("/Over").unwrap();
+        assert_eq!(over.type_name().text(), "");
+
+        let class = stage.create_class_prim("/_Class").unwrap();
+        assert_eq!(class.path().text(), "/_Class");
+    }
+
+    #[test]
+    fn define_prim_errors() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+
+        assert!(matches!(
+            stage.define_prim("World", "Xform"),
+            Err(Error::PrimDefine { .. })
+        ));
+        assert!(matches!(
+            stage.define_prim("not a path!", "Xform"),
+            Err(Error::Sdf(sdf::Error::InvalidPath { .. }))
+        ));
+        assert!(matches!(
+            stage.prim_at_path("/Missing"),
+            Err(Error::NoPrimAtPath { .. })
+        ));
+    }
+
+    #[test]
+    fn remove_prim() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage.define_prim("/World", "Xform").unwrap();
+
+        stage.remove_prim("/World").unwrap();
+        assert!(stage.prim_at_path("/World").is_err());
+    }
+
+    #[test]
+    fn create_and_remove_properties() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/Prim", "").unwrap();
+        let float3 = sdf::ValueTypeName::find("float3").unwrap();
+
+        let attr = prim
+            .create_attribute("extent", &float3, true, sdf::Variability::Varying)
+            .unwrap();
+        assert_eq!(attr.path().text(), "/Prim.extent");
+        assert!(prim.has_attribute("extent"));
+        assert!(prim.get_attribute("extent").is_ok());
+        assert!(matches!(
+            prim.get_attribute("missing"),
+            Err(Error::NoAttributeAtPath { .. })
+        ));
+
+        prim.create_relationship("material", true).unwrap();
+        assert!(prim.has_relationship("material"));
+        assert!(!prim.has_attribute("material"));
+        assert_eq!(prim.properties().size(), 2);
+
+        prim.remove_property("extent").unwrap();
+        assert!(!prim.has_attribute("extent"));
+        assert!(matches!(
+            prim.get_relationship("extent"),
+            Err(Error::NoRelationshipAtPath { .. })
+        ));
+    }
+
+    fn double_attribute(stage: &StageRefPtr) -> Attribute {
+        let prim = stage.define_prim("/Prim", "").unwrap();
+        let double = sdf::ValueTypeName::find("double").unwrap();
+        prim.create_attribute("value", &double, true, sdf::Variability::Varying)
+            .unwrap()
+    }
+
+    #[test]
+    fn time_samples() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let attr = double_attribute(&stage);
+        for time in [1.0, 2.0, 5.0] {
+            attr.set_at(&vt::ValueMember::as_value(&(time * 10.0)), time.into())
+                .unwrap();
+        }
+
+        assert_eq!(attr.time_samples(), vec![1.0, 2.0, 5.0]);
+        assert_eq!(attr.time_samples_in_interval(1.5, 5.0), vec![2.0, 5.0]);
+        assert_eq!(attr.num_time_samples(), 3);
+        assert_eq!(attr.bracketing_time_samples(3.0), Some((2.0, 5.0)));
+        assert!(attr.value_might_be_time_varying());
+        assert_eq!(
+            attr.get_at(TimeCode::new(2.0)).unwrap().get::<f64>(),
+            Some(&20.0)
+        );
+
+        attr.clear_at(2.0.into()).unwrap();
+        assert_eq!(attr.time_samples(), vec![1.0, 5.0]);
+
+        attr.clear().unwrap();
+        assert_eq!(attr.num_time_samples(), 0);
+        assert_eq!(attr.bracketing_time_samples(3.0), None);
+    }
+
+    #[test]
+    fn set_and_clear_on_expired_attribute() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let attr = double_attribute(&stage);
+        attr.set(&vt::ValueMember::as_value(&1.0)).unwrap();
+        stage.remove_prim("/Prim").unwrap();
+
+        assert!(matches!(
+            attr.set(&vt::ValueMember::as_value(&2.0)),
+            Err(Error::AttributeSet { .. })
+        ));
+        assert!(matches!(
+            attr.clear_at(TimeCode::new(1.0)),
+            Err(Error::AttributeClear { .. })
+        ));
+        assert!(matches!(attr.clear(), Err(Error::AttributeClear { .. })));
+    }
+
+    #[test]
+    fn time_code_constructors() {
+        assert!(TimeCode::default().is_default());
+        assert!(!TimeCode::new(0.0).is_default());
+        assert!(TimeCode::earliest_time().is_earliest_time());
+        assert_eq!(TimeCode::from(3.5).value(), 3.5);
+    }
+
+    #[test]
+    fn typed_get_and_set() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/Prim", "").unwrap();
+        let float = sdf::ValueTypeName::find("float").unwrap();
+        let attr = prim
+            .create_attribute("value", &float, true, sdf::Variability::Varying)
+            .unwrap();
+
+        assert!(matches!(
+            attr.get_typed::<f32>(TimeCode::default()),
+            Err(Error::NoValue { .. })
+        ));
+
+        attr.set_typed(&1.5f32, TimeCode::default()).unwrap();
+        assert_eq!(attr.get_typed::<f32>(TimeCode::default()).unwrap(), 1.5);
+
+        assert!(matches!(
+            attr.set_typed(&1i32, TimeCode::default()),
+            Err(Error::TypeMismatch { ref type_name, .. }) if type_name == "float"
+        ));
+        assert!(matches!(
+            attr.get_typed::<i32>(TimeCode::default()),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn typed_set_on_expired_attribute() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/Prim", "").unwrap();
+        let float = sdf::ValueTypeName::find("float").unwrap();
+        let attr = prim
+            .create_attribute("value", &float, true, sdf::Variability::Varying)
+            .unwrap();
+        stage.remove_prim("/Prim").unwrap();
+
+        assert!(matches!(
+            attr.set_typed(&1.5f32, TimeCode::default()),
+            Err(Error::AttributeSet { .. })
+        ));
+    }
+
+    #[test]
+    fn error_display_and_source() {
+        use std::error::Error as _;
+
+        let err = Error::NoPrimAtPath {
+            path: "/World".to_string(),
+        };
+        assert_eq!(err.to_string(), "no prim at path \"/World\"");
+        assert!(err.source().is_none());
+
+        let err = Error::from(sdf::Error::InvalidPath {
+            path: "a b".to_string(),
+        });
+        assert_eq!(err.to_string(), "invalid path \"a b\"");
+        assert!(err.source().unwrap().is::<sdf::Error>());
+    }
+
+    #[test]
+    fn stage_metadata() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        assert_eq!(stage.documentation(), "");
+
+        stage.set_documentation("docs").unwrap();
+        stage.set_comment("a comment").unwrap();
+        assert_eq!(stage.documentation(), "docs");
+        assert_eq!(stage.comment(), "a comment");
+        assert_eq!(stage.root_layer().documentation(), "docs");
+
+        let mut data = vt::Dictionary::new();
+        data.insert("pipeline:version", &vt::Value::from_member(&2i32));
+        stage.set_custom_layer_data(&data).unwrap();
+        let read = stage.custom_layer_data();
+        assert_eq!(
+            read.get("pipeline:version").unwrap().get::<i32>(),
+            Some(&2)
+        );
+        assert!(stage.root_layer().has_custom_layer_data());
+
+        stage.set_start_time_code(1.0);
+        stage.set_end_time_code(24.0);
+        stage.set_frames_per_second(24.0);
+        assert!(stage.has_authored_time_code_range());
+        assert_eq!(stage.root_layer().start_time_code(), 1.0);
+        assert_eq!(stage.root_layer().end_time_code(), 24.0);
+        assert_eq!(stage.frames_per_second(), 24.0);
+    }
+
+    #[test]
+    fn layer_stack() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let root = stage.root_layer();
+        let session = stage.session_layer();
+
+        let stack = stage.layer_stack(true);
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack[0].identifier(), session.identifier());
+        assert_eq!(stack[1].identifier(), root.identifier());
+
+        let stack = stage.layer_stack(false);
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack[0].identifier(), root.identifier());
+
+        let sub = sdf::Layer::create_anonymous("sub");
+        root.insert_sublayer_path(sub.identifier(), None).unwrap();
+        assert_eq!(stage.layer_stack(false).len(), 2);
+        assert!(stage
+            .used_layers()
+            .iter()
+            .any(|layer| layer.identifier() == sub.identifier()));
+    }
+
+    #[test]
+    fn edit_context_targets_sublayer() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let root = stage.root_layer();
+        let sub = sdf::Layer::create_anonymous("department");
+        root.insert_sublayer_path(sub.identifier(), None).unwrap();
+
+        let root_target = stage.edit_target();
+        assert!(root_target.is_valid());
+        assert_eq!(root_target.layer().identifier(), root.identifier());
+
+        {
+            let _context = stage.edit_context(&EditTarget::new(&sub.as_handle()));
+            assert_eq!(stage.edit_target().layer().identifier(), sub.identifier());
+            stage.define_prim("/World", "Xform").unwrap();
+        }
+
+        assert!(stage.edit_target() == root_target);
+        let path = sdf::Path::new("/World").unwrap();
+        assert!(sub.get_prim_at_path(&path).is_ok());
+        assert!(root.get_prim_at_path(&path).is_err());
+        assert!(stage.prim_at_path("/World").is_ok());
+    }
+
+    #[test]
+    fn stage_metadata_outside_the_root_layer_is_an_error() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let sub = sdf::Layer::create_anonymous("department");
+        stage
+            .root_layer()
+            .insert_sublayer_path(sub.identifier(), None)
+            .unwrap();
+
+        let _context = stage.edit_context(&EditTarget::new(&sub.as_handle()));
+        let err = stage.set_documentation("docs").unwrap_err();
+        assert!(matches!(err, Error::MetadataSet { .. }));
+        assert_eq!(
+            err.to_string(),
+            "failed to set metadata \"documentation\" on \"/\""
+        );
+        assert!(matches!(
+            stage.set_custom_layer_data(&vt::Dictionary::new()),
+            Err(Error::MetadataSet { .. })
+        ));
+        assert_eq!(stage.documentation(), "");
+    }
+
+    #[test]
+    fn save_writes_dirty_sublayers() {
+        let sub_path = temp_path("save_sublayer.usda");
+        std::fs::write(&sub_path, "#usda 1.0\n").unwrap();
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage
+            .root_layer()
+            .insert_sublayer_path(sub_path.to_str().unwrap(), None)
+            .unwrap();
+        let sub = sdf::Layer::find(&sub_path).unwrap();
+
+        {
+            let _context = stage.edit_context(&EditTarget::new(&sub));
+            stage.define_prim("/World", "Xform").unwrap();
+        }
+        assert!(sub.is_dirty());
+
+        stage.save().unwrap();
+        assert!(!sub.is_dirty());
+        let text = std::fs::read_to_string(&sub_path).unwrap();
+        assert!(text.contains("def Xform \"World\""));
+    }
+
+    #[test]
+    fn variant_edit_target_maps_paths() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let variant_path = sdf::Path::new("/Model{lod=high}").unwrap();
+        let target = EditTarget::for_variant(&stage.root_layer(), &variant_path);
+        assert!(target.is_valid());
+
+        let mapped = target.map_to_spec_path(&sdf::Path::new("/Model/Geo").unwrap());
+        assert_eq!(mapped.text(), "/Model{lod=high}Geo");
+
+        let plain = EditTarget::new(&stage.root_layer());
+        let mapped = plain.map_to_spec_path(&sdf::Path::new("/Model/Geo").unwrap());
+        assert_eq!(mapped.text(), "/Model/Geo");
+    }
+
+    #[test]
+    fn internal_references_and_inherits() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage.define_prim("/Source/Child", "Xform").unwrap();
+        let class = stage.create_class_prim("/_Class").unwrap();
+        let double = sdf::ValueTypeName::find("double").unwrap();
+        class
+            .create_attribute("size", &double, true, sdf::Variability::Varying)
+            .unwrap();
+
+        let target = stage.define_prim("/Target", "").unwrap();
+        assert!(!target.has_authored_references());
+        target
+            .references()
+            .add_internal_reference(
+                &sdf::Path::new("/Source").unwrap(),
+                &sdf::LayerOffset::default(),
+                ListPosition::default(),
+            )
+            .unwrap();
+        assert!(target.has_authored_references());
+        assert!(stage.prim_at_path("/Target/Child").is_ok());
+
+        target
+            .inherits()
+            .add_inherit(&sdf::Path::new("/_Class").unwrap(), ListPosition::default())
+            .unwrap();
+        assert!(target.has_authored_inherits());
+        assert!(target.has_attribute("size"));
+        assert_eq!(target.inherits().all_direct_inherits().size(), 1);
+
+        target.references().clear_references().unwrap();
+        assert!(stage.prim_at_path("/Target/Child").is_err());
+    }
+
+    #[test]
+    fn internal_payloads_and_specializes() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage.define_prim("/Source/Child", "Xform").unwrap();
+        let target = stage.define_prim("/Target", "").unwrap();
+
+        assert!(!target.has_authored_payloads());
+        target
+            .payloads()
+            .add_internal_payload(
+                &sdf::Path::new("/Source").unwrap(),
+                &sdf::LayerOffset::default(),
+                ListPosition::BackOfAppendList,
+            )
+            .unwrap();
+        assert!(target.has_authored_payloads());
+        assert!(target.is_loaded());
+        assert!(stage.prim_at_path("/Target/Child").is_ok());
+
+        target
+            .specializes()
+            .add_specialize(&sdf::Path::new("/Source").unwrap(), ListPosition::default())
+            .unwrap();
+        assert!(target.has_authored_specializes());
+        target.specializes().clear_specializes().unwrap();
+        assert!(!target.has_authored_specializes());
+    }
+
+    #[test]
+    fn composition_edits_on_expired_prim_are_errors() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/Prim", "").unwrap();
+        stage.remove_prim("/Prim").unwrap();
+
+        let err = prim
+            .inherits()
+            .add_inherit(&sdf::Path::new("/_Class").unwrap(), ListPosition::default())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CompositionEdit { ref path, arc: "inherits" } if path == "/Prim"
+        ));
+        assert!(prim.references().clear_references().is_err());
+    }
+
+    #[test]
+    fn variant_edit_context() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let model = stage.define_prim("/Model", "").unwrap();
+        let lod = model
+            .variant_sets()
+            .add_variant_set("lod", ListPosition::default())
+            .unwrap();
+        lod.add_variant("high", ListPosition::default()).unwrap();
+        lod.add_variant("low", ListPosition::default()).unwrap();
+        assert_eq!(lod.variant_names(), ["high", "low"]);
+
+        lod.set_variant_selection("high").unwrap();
+        {
+            let _context = lod.variant_edit_context(&stage).unwrap();
+            stage.define_prim("/Model/HighGeo", "").unwrap();
+        }
+        assert!(stage.prim_at_path("/Model/HighGeo").is_ok());
+        assert!(stage
+            .root_layer()
+            .get_prim_at_path(&sdf::Path::new("/Model{lod=high}HighGeo").unwrap())
+            .is_ok());
+
+        lod.set_variant_selection("low").unwrap();
+        assert_eq!(lod.variant_selection(), "low");
+        assert!(stage.prim_at_path("/Model/HighGeo").is_err());
+
+        lod.clear_variant_selection().unwrap();
+        assert!(!lod.has_authored_variant_selection());
+    }
+
+    #[test]
+    fn variant_edits_on_expired_prim_are_errors() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let model = stage.define_prim("/Model", "").unwrap();
+        let lod = model
+            .variant_sets()
+            .add_variant_set("lod", ListPosition::default())
+            .unwrap();
+        assert_eq!(lod.name(), "lod");
+        stage.remove_prim("/Model").unwrap();
+
+        let err = lod
+            .add_variant("high", ListPosition::default())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to edit variant set \"lod\" on \"/Model\""
+        );
+        assert!(matches!(
+            lod.set_variant_selection("high"),
+            Err(Error::VariantSetEdit { .. })
+        ));
+        assert!(matches!(
+            lod.clear_variant_selection(),
+            Err(Error::VariantSetEdit { .. })
+        ));
+    }
+
+    #[test]
+    fn variant_edit_context_on_other_stage_is_an_error() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let other = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        other.define_prim("/Model", "").unwrap();
+        let model = stage.define_prim("/Model", "").unwrap();
+        let lod = model
+            .variant_sets()
+            .add_variant_set("lod", ListPosition::default())
+            .unwrap();
+
+        assert!(matches!(
+            lod.variant_edit_context(&other),
+            Err(Error::PrimNotOnStage { .. })
+        ));
+        assert!(lod.variant_edit_context(&stage).is_ok());
+    }
+
+    /// Write a shot layer whose `/Model` prim has a payload on a separate asset layer.
+    fn write_payload_shot(name: &str) -> std::path::PathBuf {
+        let asset = temp_path(&format!("{name}_asset.usda"));
+        std::fs::write(
+            &asset,
+            "#usda 1.0\n\ndef \"Asset\"\n{\n    def \"Geo\"\n    {\n    }\n}\n",
+        )
+        .unwrap();
+
+        let shot = temp_path(&format!("{name}_shot.usda"));
+        std::fs::write(
+            &shot,
+            format!(
+                "#usda 1.0\n\ndef \"Model\" (\n    payload = @{}@</Asset>\n)\n{{\n}}\n",
+                asset.display()
+            ),
+        )
+        .unwrap();
+        shot
+    }
+
+    #[test]
+    fn open_with_load_none_and_load() {
+        let shot = write_payload_shot("load_none");
+        let stage = Stage::open(&shot, InitialLoadSet::LoadNone).unwrap();
+
+        let model = stage.prim_at_path("/Model").unwrap();
+        assert!(model.has_authored_payloads());
+        assert!(!model.is_loaded());
+        assert!(stage.prim_at_path("/Model/Geo").is_err());
+        assert!(stage.get_loaded_paths().is_empty());
+        assert_eq!(
+            stage.find_loadable("/").unwrap(),
+            vec![sdf::Path::new("/Model").unwrap()]
+        );
+
+        let model = stage.load("/Model", LoadPolicy::default()).unwrap();
+        assert!(model.is_loaded());
+        assert!(stage.prim_at_path("/Model/Geo").is_ok());
+        assert_eq!(stage.get_loaded_paths(), vec![sdf::Path::new("/Model").unwrap()]);
+
+        stage.unload("/Model").unwrap();
+        assert!(!stage.prim_at_path("/Model").unwrap().is_loaded());
+    }
+
+    #[test]
+    fn load_and_unload_sets() {
+        let shot = write_payload_shot("load_and_unload");
+        let stage = Stage::open(&shot, InitialLoadSet::LoadAll).unwrap();
+        let model = sdf::Path::new("/Model").unwrap();
+        assert!(stage.prim_at_path("/Model/Geo").is_ok());
+
+        stage.load_and_unload(&[], &[model.clone()], LoadPolicy::default());
+        assert!(stage.prim_at_path("/Model/Geo").is_err());
+
+        stage.load_and_unload(&[model], &[], LoadPolicy::default());
+        assert!(stage.prim_at_path("/Model/Geo").is_ok());
+    }
+
+    #[test]
+    fn population_mask() {
+        let mask: StagePopulationMask = ["/World/A", "/World/B", "/World/A/Child"]
+            .iter()
+            .map(|p| sdf::Path::new(p).unwrap())
+            .collect();
+        assert!(!mask.is_empty());
+        assert_eq!(mask.paths().size(), 2);
+        assert_eq!(format!("{:?}", mask), r#"["/World/A", "/World/B"]"#);
+
+        assert!(mask.includes(&sdf::Path::new("/World").unwrap()));
+        assert!(mask.includes(&sdf::Path::new("/World/A/Child").unwrap()));
+        assert!(!mask.includes(&sdf::Path::new("/Other").unwrap()));
+        assert!(mask.includes_subtree(&sdf::Path::new("/World/A").unwrap()));
+        assert!(!mask.includes_subtree(&sdf::Path::new("/World").unwrap()));
+
+        let other: StagePopulationMask = [sdf::Path::new("/World/B/X").unwrap()]
+            .into_iter()
+            .collect();
+        assert_eq!(mask.intersection(&other), other);
+        assert_eq!(mask.union(&other), mask);
+        assert!(StagePopulationMask::new().is_empty());
+        assert!(StagePopulationMask::all().includes_subtree(&sdf::Path::absolute_root()));
+    }
+
+    #[test]
+    fn open_masked_stage() {
+        let path = temp_path("masked.usda");
+        let stage = Stage::create_new(&path, InitialLoadSet::LoadAll).unwrap();
+        stage.define_prim("/World/A", "").unwrap();
+        stage.define_prim("/World/B", "").unwrap();
+        stage.save().unwrap();
+        drop(stage);
+
+        let mask: StagePopulationMask = [sdf::Path::new("/World/A").unwrap()].into_iter().collect();
+        let options = OpenOptions::new(&path)
+            .mask(&mask)
+            .load_set(InitialLoadSet::LoadNone);
+        let debug = format!("{:?}", options);
+        assert!(debug.starts_with("OpenOptions { filename: "));
+        assert!(debug.contains(r#"mask: Some(["/World/A"])"#));
+        assert!(debug.contains("load_set: LoadNone"));
+
+        let stage = options.open().unwrap();
+        assert!(stage.prim_at_path("/World/A").is_ok());
+        assert!(stage.prim_at_path("/World/B").is_err());
+    }
+
+    #[test]
+    fn prim_flags_and_or() {
+        use PrimFlag::*;
+
+        let conjunction = Active & !Abstract & Defined;
+        assert_eq!(conjunction, PrimFlagsConjunction::from(Defined) & Active & !Abstract);
+        assert_eq!(
+            PrimFlagsPredicate::from(Active & Loaded & Defined & !Abstract),
+            PrimFlagsPredicate::default()
+        );
+
+        let disjunction = Model | !Group;
+        assert_eq!(disjunction, PrimFlagsDisjunction::from(!Group) | Model);
+        assert_ne!(
+            PrimFlagsPredicate::from(Model & Group),
+            PrimFlagsPredicate::from(Model | Group)
+        );
+        assert_eq!(PrimFlagsPredicate::from(Model), PrimFlagsPredicate::from(Model & Model));
+    }
+
+    #[test]
+    fn prim_flags_not_and_de_morgan() {
+        use PrimFlag::*;
+
+        assert_eq!(!!Active, PrimFlagsTerm::from(Active));
+        assert_eq!(!(Active & Loaded), !Active | !Loaded);
+        assert_eq!(!(Active | Loaded), !Active & !Loaded);
+        assert_eq!(!(Active & !Loaded), !Active | Loaded);
+        assert_eq!(!!(Active & Loaded), Active & Loaded);
+        assert_eq!(!!(Active | Loaded), Active | Loaded);
+        assert_ne!(
+            PrimFlagsPredicate::from(!(Active & Loaded)),
+            PrimFlagsPredicate::from(Active & Loaded)
+        );
+    }
+
+    #[test]
+    fn prim_flags_contradiction() {
+        use PrimFlag::*;
+
+        assert_eq!(Active & !Active, Loaded & !Loaded & Model);
+        assert_eq!(Active & !Active & Active, Active & !Active);
+        assert_ne!(Active & !Active, PrimFlagsConjunction::from(Active));
+
+        let nothing = PrimFlagsPredicate::from(Active & !Active);
+        assert_eq!(nothing, PrimFlagsPredicate::from(!PrimFlagsConjunction::TAUTOLOGY));
+        let everything = PrimFlagsPredicate::from(Active | !Active);
+        assert_eq!(everything, PrimFlagsPredicate::all());
+        assert_eq!(PrimFlagsPredicate::from(!(Active & !Active)), everything);
+    }
+
+    #[test]
+    fn traverse_with_prim_flags() {
+        use PrimFlag::*;
+
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage.define_prim("/Concrete", "").unwrap();
+        stage.create_class_prim("/_Class").unwrap();
+
+        let paths = |predicate: PrimFlagsPredicate| -> Vec<String> {
+            stage
+                .traverse_filtered(predicate)
+                .map(|prim| prim.path().text().to_string())
+                .collect()
+        };
+
+        assert_eq!(paths(Abstract.into()), ["/_Class"]);
+        assert_eq!(paths((!Abstract).into()), ["/Concrete"]);
+        assert_eq!(paths((!(Abstract | !Defined)).into()), ["/Concrete"]);
+        assert_eq!(paths((Abstract | !Abstract).into()), ["/Concrete", "/_Class"]);
+        assert!(paths((Abstract & !Abstract).into()).is_empty());
+    }
+
+    fn visit_label(visit: &Visit) -> String {
+        match visit {
+            Visit::Pre(prim) => format!("pre {}", prim.name()),
+            Visit::Post(prim) => format!("post {}", prim.name()),
+        }
+    }
+
+    #[test]
+    fn pre_and_post_visit() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let a = stage.define_prim("/A", "").unwrap();
+        stage.define_prim("/A/B", "").unwrap();
+        stage.override_prim("/A/Over").unwrap();
+
+        // The default predicate skips prims that are not defined.
+        let labels: Vec<String> = PrimRange::pre_and_post_visit(&a)
+            .map(|visit| visit_label(&visit))
+            .collect();
+        assert_eq!(labels, ["pre A", "pre B", "post B", "post A"]);
+
+        let labels: Vec<String> =
+            PrimRange::pre_and_post_visit_with_predicate(&a, PrimFlagsPredicate::all())
+                .map(|visit| visit_label(&visit))
+                .collect();
+        assert_eq!(
+            labels,
+            ["pre A", "pre B", "post B", "pre Over", "post Over", "post A"]
+        );
+    }
+
+    #[test]
+    fn pre_and_post_visit_prune_children() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let a = stage.define_prim("/A", "").unwrap();
+        stage.define_prim("/A/B/C", "").unwrap();
+
+        let mut range = PrimRange::pre_and_post_visit(&a);
+        let mut labels = Vec::new();
+        while let Some(visit) = range.next() {
+            let label = visit_label(&visit);
+            // Pruning B on its pre visit skips C; pruning after a post visit does nothing.
+            if label == "pre B" || label == "post B" {
+                range.prune_children();
+            }
+            labels.push(label);
+        }
+        assert_eq!(labels, ["pre A", "pre B", "post B", "post A"]);
+    }
+
+    #[test]
+    fn set_active_deactivates_prim() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/A", "").unwrap();
+        stage.define_prim("/A/B", "").unwrap();
+        stage.define_prim("/C", "").unwrap();
+
+        prim.set_active(false).unwrap();
+        assert!(!prim.is_active());
+        let paths: Vec<String> = stage
+            .traverse()
+            .map(|prim| prim.path().text().to_string())
+            .collect();
+        assert_eq!(paths, ["/C"]);
+
+        prim.set_active(true).unwrap();
+        assert!(prim.is_active());
+        assert_eq!(stage.traverse().count(), 3);
+    }
+
+    #[test]
+    fn set_active_on_pseudo_root_is_an_error() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        assert!(matches!(
+            stage.pseudo_root().set_active(false),
+            Err(Error::PrimSetActive { .. })
+        ));
+    }
+
+    #[test]
+    fn is_a_typed_schema() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        crate::usd_geom::Mesh::define(&stage.as_weak(), &sdf::Path::new("/Mesh").unwrap());
+        stage.define_prim("/Xform", "Xform").unwrap();
+
+        assert!(stage.prim_at_path("/Mesh").unwrap().is_a::<crate::usd_geom::Mesh>());
+        assert!(!stage.prim_at_path("/Xform").unwrap().is_a::<crate::usd_geom::Mesh>());
+    }
+
+    #[test]
+    fn has_api_and_applied_schemas() {
+        struct GeomModelApi;
+
+        impl SchemaBase for GeomModelApi {
+            const SCHEMA_IDENTIFIER: &'static str = "GeomModelAPI";
+        }
+
+        impl ApiSchema for GeomModelApi {}
+
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        stage
+            .root_layer()
+            .import_from_string(
+                "#usda 1.0\n\ndef Xform \"Model\" (\n    prepend apiSchemas = [\"GeomModelAPI\"]\n)\n{\n}\n\ndef Xform \"Plain\"\n{\n}\n",
+            )
+            .unwrap();
+
+        let model = stage.prim_at_path("/Model").unwrap();
+        assert!(model.has_api::<GeomModelApi>());
+        assert!(model
+            .applied_schemas()
+            .iter()
+            .any(|schema| schema.text() == "GeomModelAPI"));
+        assert!(!stage.prim_at_path("/Plain").unwrap().has_api::<GeomModelApi>());
+    }
+
+    #[test]
+    fn object_metadata_edits() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/World", "").unwrap();
+
+        assert!(!prim.is_hidden());
+        prim.set_hidden(true).unwrap();
+        assert!(prim.is_hidden());
+        assert!(prim.has_authored_metadata("hidden"));
+        prim.clear_metadata("hidden").unwrap();
+        assert!(!prim.has_authored_metadata("hidden"));
+
+        prim.set_documentation("the world").unwrap();
+        assert_eq!(prim.documentation(), "the world");
+
+        prim.set_custom_data_by_key("render:quality", &vt::Value::from_member(&3i32))
+            .unwrap();
+        let value = prim
+            .get_metadata_by_dict_key("customData", "render:quality")
+            .unwrap();
+        assert_eq!(value.get::<i32>().copied(), Some(3));
+        assert!(prim.custom_data().contains_key("render:quality"));
+
+        prim.clear_metadata_by_dict_key("customData", "render:quality")
+            .unwrap();
+        assert!(prim
+            .get_metadata_by_dict_key("customData", "render:quality")
+            .is_none());
+        assert!(prim.asset_info().is_empty());
+    }
+
+    #[test]
+    fn set_unregistered_metadata_is_an_error() {
+        let stage = Stage::create_in_memory(InitialLoadSet::LoadAll);
+        let prim = stage.define_prim("/World", "").unwrap();
+
+        let err = prim
+            .set_metadata("notAField", &vt::Value::from_member(&1i32))
+            .unwrap_err();
+        assert!(matches!(err, Error::MetadataSet { .. }));
+        assert_eq!(
+            err.to_string(),
+            "failed to set metadata \"notAField\" on \"/World\""
+        );
+    }
+}
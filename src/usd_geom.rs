@@ -1,11 +1,89 @@
 use crate::ffi;
 use crate::sdf;
+use crate::tf;
 use crate::usd;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpAxis {
+    Y,
+    Z,
+}
+
+impl UpAxis {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UpAxis::Y => "Y",
+            UpAxis::Z => "Z",
+        }
+    }
+}
+
+/// The stage's `upAxis` metadata, or the site fallback if it is not authored.
+pub fn get_stage_up_axis(stage: &usd::StageWeakPtr) -> UpAxis {
+    unsafe {
+        let mut ptr = std::ptr::null_mut();
+        ffi::usdGeom_GetStageUpAxis(stage.ptr, &mut ptr);
+        let token = tf::Token { ptr };
+        if token.text() == "Z" {
+            UpAxis::Z
+        } else {
+            UpAxis::Y
+        }
+    }
+}
+
+pub fn set_stage_up_axis(stage: &usd::StageWeakPtr, axis: UpAxis) -> Result<(), usd::Error> {
+    let token = tf::Token::new(axis.as_str());
+    let result = unsafe {
+        let mut result = false;
+        ffi::usdGeom_SetStageUpAxis(stage.ptr, token.ptr, &mut result);
+        result
+    };
+    stage_metadata_result(result, "upAxis")
+}
+
+/// The stage's `metersPerUnit` metadata, or the fallback of 0.01 (centimeters).
+pub fn get_stage_meters_per_unit(stage: &usd::StageWeakPtr) -> f64 {
+    unsafe {
+        let mut result = 0.0;
+        ffi::usdGeom_GetStageMetersPerUnit(stage.ptr, &mut result);
+        result
+    }
+}
+
+pub fn set_stage_meters_per_unit(
+    stage: &usd::StageWeakPtr,
+    meters_per_unit: f64,
+) -> Result<(), usd::Error> {
+    let result = unsafe {
+        let mut result = false;
+        ffi::usdGeom_SetStageMetersPerUnit(stage.ptr, meters_per_unit, &mut result);
+        result
+    };
+    stage_metadata_result(result, "metersPerUnit")
+}
+
+fn stage_metadata_result(result: bool, key: &str) -> Result<(), usd::Error> {
+    if result {
+        Ok(())
+    } else {
+        Err(usd::Error::MetadataSet {
+            path: "/".to_string(),
+            key: key.to_string(),
+        })
+    }
+}
+
 pub struct Mesh {
     pub(crate) ptr: *mut ffi::usdGeom_Mesh_t,
 }
 
+impl usd::SchemaBase for Mesh {
+    const SCHEMA_IDENTIFIER: &'static str = "Mesh";
+}
+
+impl usd::TypedSchema for Mesh {}
+
 impl Mesh {
     pub fn define(stage: &usd::StageWeakPtr, path: &sdf::Path) -> Mesh {
         unsafe {
@@ -47,3 +125,40 @@ impl Mesh {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_up_axis_and_meters_per_unit() {
+        let stage = usd::Stage::create_in_memory(usd::InitialLoadSet::LoadAll);
+        let weak = stage.as_weak();
+
+        set_stage_up_axis(&weak, UpAxis::Z).unwrap();
+        assert_eq!(get_stage_up_axis(&weak), UpAxis::Z);
+        set_stage_meters_per_unit(&weak, 1.0).unwrap();
+        assert_eq!(get_stage_meters_per_unit(&weak), 1.0);
+    }
+
+    #[test]
+    fn stage_metadata_on_a_sublayer_edit_target_is_an_error() {
+        let stage = usd::Stage::create_in_memory(usd::InitialLoadSet::LoadAll);
+        let sub = sdf::Layer::create_anonymous("sub");
+        stage
+            .root_layer()
+            .insert_sublayer_path(sub.identifier(), None)
+            .unwrap();
+
+        let _context = stage.edit_context(&usd::EditTarget::new(&sub.as_handle()));
+        let weak = stage.as_weak();
+        assert!(matches!(
+            set_stage_up_axis(&weak, UpAxis::Z),
+            Err(usd::Error::MetadataSet { .. })
+        ));
+        assert!(matches!(
+            set_stage_meters_per_unit(&weak, 1.0),
+            Err(usd::Error::MetadataSet { .. })
+        ));
+    }
+}
@@ -1,13 +1,31 @@
-use std::ops::IndexMut;
-
-use crate::{ffi, sdf, tf};
-use glam::{DVec3, Vec2, Vec3, Vec4};
+use crate::{cpp, ffi, sdf, tf, usd};
+use glam::{
+    DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Quat, Vec2, Vec3, Vec4,
+};
+use half::f16;
+use paste::paste;
+use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 
 pub struct TokenArray {
     pub(crate) ptr: *mut ffi::vt_TokenArray_t,
 }
 
 impl TokenArray {
+    pub fn new(values: &[&str]) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_TokenArray_default(&mut ptr);
+            for value in values {
+                let token = tf::Token::new(value);
+                ffi::vt_TokenArray_push_back(ptr, token.ptr);
+            }
+            TokenArray { ptr }
+        }
+    }
+
     pub fn size(&self) -> usize {
         unsafe {
             let mut result = 0;
@@ -16,11 +34,11 @@ impl TokenArray {
         }
     }
 
-    pub fn at(&self, index: usize) -> tf::TokenRef {
+    pub fn at(&self, index: usize) -> tf::TokenRef<'_> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
             ffi::vt_TokenArray_op_index(self.ptr, index, &mut ptr);
-            tf::TokenRef { ptr }
+            tf::TokenRef::from_ptr(ptr)
         }
     }
 
@@ -34,7 +52,7 @@ impl TokenArray {
 }
 
 impl<'a> IntoIterator for &'a TokenArray {
-    type Item = tf::TokenRef;
+    type Item = tf::TokenRef<'a>;
     type IntoIter = TokenArrayIterator<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -49,7 +67,7 @@ pub struct TokenArrayIterator<'a> {
 }
 
 impl<'a> Iterator for TokenArrayIterator<'a> {
-    type Item = tf::TokenRef;
+    type Item = tf::TokenRef<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current == self.end {
@@ -62,11 +80,25 @@ impl<'a> Iterator for TokenArrayIterator<'a> {
     }
 }
 
-pub struct TokenArrayRef {
+/// A borrowed [`TokenArray`] held by another object, such as a [`Value`].
+#[repr(transparent)]
+pub struct TokenArrayRef<'a> {
     pub(crate) ptr: *const ffi::vt_TokenArray_t,
+    _marker: PhantomData<&'a TokenArray>,
+}
+
+impl<'a> TokenArrayRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid object that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::vt_TokenArray_t) -> Self {
+        TokenArrayRef {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
 }
 
-impl std::ops::Deref for TokenArrayRef {
+impl<'a> std::ops::Deref for TokenArrayRef<'a> {
     type Target = TokenArray;
 
     fn deref(&self) -> &Self::Target {
@@ -74,38 +106,51 @@ impl std::ops::Deref for TokenArrayRef {
     }
 }
 
-pub struct IntArray {
-    pub(crate) ptr: *mut ffi::vt_IntArray_t,
+impl Drop for TokenArray {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::vt_TokenArray_dtor(self.ptr);
+        }
+    }
+}
+
+pub struct StringArray {
+    pub(crate) ptr: *mut ffi::vt_StringArray_t,
 }
 
-impl IntArray {
-    pub fn new(values: &[i32]) -> Self {
+impl StringArray {
+    pub fn new(values: &[&str]) -> Self {
         unsafe {
-            let range = values.as_ptr_range();
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_IntArray_from_range(range.start, range.end, &mut ptr);
-            IntArray { ptr }
+            ffi::vt_StringArray_default(&mut ptr);
+            for value in values {
+                let string = cpp::String::new(value);
+                ffi::vt_StringArray_push_back(ptr, string.ptr);
+            }
+            StringArray { ptr }
         }
     }
 
     pub fn size(&self) -> usize {
         unsafe {
             let mut result = 0;
-            ffi::vt_IntArray_size(self.ptr, &mut result);
+            ffi::vt_StringArray_size(self.ptr, &mut result);
             result
         }
     }
 
-    pub fn at(&self, index: usize) -> &i32 {
+    pub fn at(&self, index: usize) -> &str {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::vt_IntArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut i32)
+            let mut ptr = std::ptr::null();
+            ffi::vt_StringArray_op_index(self.ptr, index, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            CStr::from_ptr(ptr_c_str).to_str().unwrap()
         }
     }
 
-    pub fn iter(&self) -> IntArrayIterator {
-        IntArrayIterator {
+    pub fn iter(&self) -> StringArrayIterator {
+        StringArrayIterator {
             vec: self,
             current: 0,
             end: self.size(),
@@ -113,23 +158,23 @@ impl IntArray {
     }
 }
 
-impl<'a> IntoIterator for &'a IntArray {
-    type Item = &'a i32;
-    type IntoIter = IntArrayIterator<'a>;
+impl<'a> IntoIterator for &'a StringArray {
+    type Item = &'a str;
+    type IntoIter = StringArrayIterator<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-pub struct IntArrayIterator<'a> {
-    vec: &'a IntArray,
+pub struct StringArrayIterator<'a> {
+    vec: &'a StringArray,
     current: usize,
     end: usize,
 }
 
-impl<'a> Iterator for IntArrayIterator<'a> {
-    type Item = &'a i32;
+impl<'a> Iterator for StringArrayIterator<'a> {
+    type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current == self.end {
@@ -142,517 +187,761 @@ impl<'a> Iterator for IntArrayIterator<'a> {
     }
 }
 
-pub struct IntArrayRef {
-    pub(crate) ptr: *const ffi::vt_IntArray_t,
+/// A borrowed [`StringArray`] held by another object, such as a [`Value`].
+#[repr(transparent)]
+pub struct StringArrayRef<'a> {
+    pub(crate) ptr: *const ffi::vt_StringArray_t,
+    _marker: PhantomData<&'a StringArray>,
 }
 
-impl std::ops::Deref for IntArrayRef {
-    type Target = IntArray;
-
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const IntArrayRef as *const IntArray) }
+impl<'a> StringArrayRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid object that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::vt_StringArray_t) -> Self {
+        StringArrayRef {
+            ptr,
+            _marker: PhantomData,
+        }
     }
 }
 
-pub struct FloatArray {
-    pub(crate) ptr: *mut ffi::vt_FloatArray_t,
-}
+impl<'a> std::ops::Deref for StringArrayRef<'a> {
+    type Target = StringArray;
 
-impl FloatArray {
-    pub fn size(&self) -> usize {
-        unsafe {
-            let mut result = 0;
-            ffi::vt_FloatArray_size(self.ptr, &mut result);
-            result
-        }
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const StringArrayRef as *const StringArray) }
     }
+}
 
-    pub fn at(&self, index: usize) -> &f32 {
+impl Drop for StringArray {
+    fn drop(&mut self) {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::vt_FloatArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut f32)
+            ffi::vt_StringArray_dtor(self.ptr);
         }
     }
+}
 
-    pub fn iter(&self) -> FloatArrayIterator {
-        FloatArrayIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
-        }
-    }
+/// Quaternion of half-precision floats, laid out to match GfQuath (imaginary then real).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Quath {
+    pub x: f16,
+    pub y: f16,
+    pub z: f16,
+    pub w: f16,
 }
 
-impl<'a> IntoIterator for &'a FloatArray {
-    type Item = &'a f32;
-    type IntoIter = FloatArrayIterator<'a>;
+/// A `VtArray<T>` of plain-old-data elements whose memory layout matches the Rust element
+/// type, so that the array's storage can be viewed directly as a slice.
+///
+/// Cloning an `Array` is cheap: like `VtArray`, the storage is shared until one of the copies
+/// is mutated.
+#[repr(transparent)]
+pub struct Array<T: ArrayElement> {
+    pub(crate) ptr: *mut T::RawArray,
+    _marker: PhantomData<T>,
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+impl<T: ArrayElement> Array<T> {
+    pub fn new() -> Self {
+        unsafe { Array::from_ptr(T::array_default()) }
     }
-}
 
-pub struct FloatArrayIterator<'a> {
-    vec: &'a FloatArray,
-    current: usize,
-    end: usize,
-}
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut array = Array::new();
+        array.reserve(capacity);
+        array
+    }
 
-impl<'a> Iterator for FloatArrayIterator<'a> {
-    type Item = &'a f32;
+    pub fn from_slice(values: &[T]) -> Self {
+        unsafe { Array::from_ptr(T::array_from_slice(values)) }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
+    pub(crate) unsafe fn from_ptr(ptr: *mut T::RawArray) -> Self {
+        Array {
+            ptr,
+            _marker: PhantomData,
         }
     }
-}
 
-pub struct FloatArrayRef {
-    pub(crate) ptr: *const ffi::vt_FloatArray_t,
-}
+    pub fn len(&self) -> usize {
+        unsafe { T::array_size(self.ptr) }
+    }
 
-impl std::ops::Deref for FloatArrayRef {
-    type Target = FloatArray;
+    pub fn size(&self) -> usize {
+        self.len()
+    }
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const FloatArrayRef as *const FloatArray) }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-}
 
-pub struct DoubleArray {
-    pub(crate) ptr: *mut ffi::vt_DoubleArray_t,
-}
+    pub fn capacity(&self) -> usize {
+        unsafe { T::array_capacity(self.ptr) }
+    }
 
-impl DoubleArray {
-    pub fn size(&self) -> usize {
-        unsafe {
-            let mut result = 0;
-            ffi::vt_DoubleArray_size(self.ptr, &mut result);
-            result
-        }
+    pub fn push(&mut self, value: T) {
+        unsafe { T::array_push_back(self.ptr, &value) }
     }
 
-    pub fn at(&self, index: usize) -> &f64 {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::vt_DoubleArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut f64)
+    /// Resize the array to `len` elements, value-initializing any new elements.
+    pub fn resize(&mut self, len: usize) {
+        unsafe { T::array_resize(self.ptr, len) }
+    }
+
+    pub fn reserve(&mut self, capacity: usize) {
+        unsafe { T::array_reserve(self.ptr, capacity) }
+    }
+
+    pub fn clear(&mut self) {
+        unsafe { T::array_clear(self.ptr) }
+    }
+
+    pub fn at(&self, index: usize) -> &T {
+        &self.as_slice()[index]
+    }
+
+    /// View the array's contiguous storage without copying.
+    pub fn as_slice(&self) -> &[T] {
+        let len = self.len();
+        if len == 0 {
+            return &[];
         }
+
+        unsafe { std::slice::from_raw_parts(T::array_cdata(self.ptr), len) }
     }
 
-    pub fn iter(&self) -> DoubleArrayIterator {
-        DoubleArrayIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
+    /// Mutably view the array's storage. If the storage is shared with another array it is
+    /// copied first, as with `VtArray::data()`.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        if len == 0 {
+            return &mut [];
         }
+
+        unsafe { std::slice::from_raw_parts_mut(T::array_data(self.ptr), len) }
     }
-}
 
-impl<'a> IntoIterator for &'a DoubleArray {
-    type Item = &'a f64;
-    type IntoIter = DoubleArrayIterator<'a>;
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
     }
 }
 
-pub struct DoubleArrayIterator<'a> {
-    vec: &'a DoubleArray,
-    current: usize,
-    end: usize,
+impl<T: ArrayElement> Default for Array<T> {
+    fn default() -> Self {
+        Array::new()
+    }
 }
 
-impl<'a> Iterator for DoubleArrayIterator<'a> {
-    type Item = &'a f64;
+impl<T: ArrayElement> Clone for Array<T> {
+    fn clone(&self) -> Self {
+        unsafe { Array::from_ptr(T::array_copy(self.ptr)) }
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
-        }
+impl<T: ArrayElement + PartialEq> PartialEq for Array<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
     }
 }
 
-pub struct DoubleArrayRef {
-    pub(crate) ptr: *const ffi::vt_DoubleArray_t,
+impl<T: ArrayElement + fmt::Debug> fmt::Debug for Array<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
 }
 
-impl std::ops::Deref for DoubleArrayRef {
-    type Target = DoubleArray;
+impl<T: ArrayElement> Index<usize> for Array<T> {
+    type Output = T;
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const DoubleArrayRef as *const DoubleArray) }
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
     }
 }
 
-pub struct Vec2Array {
-    pub(crate) ptr: *mut ffi::gf_Vec2fArray_t,
+impl<T: ArrayElement> IndexMut<usize> for Array<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.as_mut_slice()[index]
+    }
 }
 
-impl Vec2Array {
-    pub fn size(&self) -> usize {
-        unsafe {
-            let mut result = 0;
-            ffi::gf_Vec2fArray_size(self.ptr, &mut result);
-            result
-        }
-    }
+impl<'a, T: ArrayElement> IntoIterator for &'a Array<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
 
-    pub fn at(&self, index: usize) -> &Vec2 {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::gf_Vec2fArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut Vec2)
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
 
-    pub fn iter(&self) -> Vec2ArrayIterator {
-        Vec2ArrayIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
-        }
+impl<'a, T: ArrayElement> IntoIterator for &'a mut Array<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
-impl<'a> IntoIterator for &'a Vec2Array {
-    type Item = &'a Vec2;
-    type IntoIter = Vec2ArrayIterator<'a>;
+impl<T: ArrayElement> From<&[T]> for Array<T> {
+    fn from(values: &[T]) -> Self {
+        Array::from_slice(values)
+    }
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+impl<T: ArrayElement> From<Vec<T>> for Array<T> {
+    fn from(values: Vec<T>) -> Self {
+        Array::from_slice(&values)
     }
 }
 
-pub struct Vec2ArrayIterator<'a> {
-    vec: &'a Vec2Array,
-    current: usize,
-    end: usize,
+impl<T: ArrayElement> FromIterator<T> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        Array::from_slice(&values)
+    }
 }
 
-impl<'a> Iterator for Vec2ArrayIterator<'a> {
-    type Item = &'a Vec2;
+impl<T: ArrayElement> Extend<T> for Array<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
+impl<T: ArrayElement> Drop for Array<T> {
+    fn drop(&mut self) {
+        unsafe {
+            T::array_dtor(self.ptr);
         }
     }
 }
 
-pub struct Vec2ArrayRef {
-    pub(crate) ptr: *const ffi::gf_Vec2fArray_t,
+/// A borrowed view of an [`Array`] held by another object, such as a [`Value`].
+#[repr(transparent)]
+pub struct ArrayRef<'a, T: ArrayElement> {
+    pub(crate) ptr: *const T::RawArray,
+    _marker: PhantomData<&'a Array<T>>,
 }
 
-impl std::ops::Deref for Vec2ArrayRef {
-    type Target = Vec2Array;
+impl<'a, T: ArrayElement> std::ops::Deref for ArrayRef<'a, T> {
+    type Target = Array<T>;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const Vec2ArrayRef as *const Vec2Array) }
+        unsafe { &*(self as *const ArrayRef<'a, T> as *const Array<T>) }
     }
 }
 
-pub struct Vec3Array {
-    pub(crate) ptr: *mut ffi::gf_Vec3fArray_t,
-}
+impl<T: ArrayElement> ValueMember for Array<T> {
+    type Ref<'a> = ArrayRef<'a, T>
+    where
+        T: 'a;
 
-impl Vec3Array {
-    pub fn new(data: &[Vec3]) -> Self {
-        unsafe {
-            let range = data.as_ptr_range();
-            let mut ptr = std::ptr::null_mut();
-            ffi::gf_Vec3fArray_from_range(
-                range.start as *const ffi::gf_Vec3f_t,
-                range.end as *const ffi::gf_Vec3f_t,
-                &mut ptr,
-            );
-            Vec3Array { ptr }
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
+        if Self::is_holding(value) {
+            unsafe {
+                Some(ArrayRef {
+                    ptr: T::value_get(value.ptr),
+                    _marker: PhantomData,
+                })
+            }
+        } else {
+            None
         }
     }
 
-    pub fn size(&self) -> usize {
+    fn is_holding(value: &Value) -> bool {
+        unsafe { T::value_is_holding(value.ptr) }
+    }
+
+    fn as_value(member: &Self) -> Value {
         unsafe {
-            let mut result = 0;
-            ffi::gf_Vec3fArray_size(self.ptr, &mut result);
-            result
+            Value {
+                ptr: T::value_from(member.ptr),
+            }
         }
     }
 
-    pub fn at(&self, index: usize) -> &Vec3 {
+    fn tf_type() -> tf::Type {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::gf_Vec3fArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut Vec3)
+            tf::Type {
+                ptr: T::value_type(),
+            }
         }
     }
 
-    pub fn iter(&self) -> Vec3ArrayIterator {
-        Vec3ArrayIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        let array = Array::new();
+        if unsafe { T::attribute_get(attr.ptr, array.ptr, time) } {
+            Some(array)
+        } else {
+            None
         }
     }
-}
-
-impl<'a> IntoIterator for &'a Vec3Array {
-    type Item = &'a Vec3;
-    type IntoIter = Vec3ArrayIterator<'a>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
-    }
-}
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
+        unsafe { T::attribute_set(attr.ptr, member.ptr, time) }
+    }
+}
+
+/// An element type that can be stored in an [`Array`].
+///
+/// Implementations are generated by the `array_element!` macro, one per bound `VtArray`
+/// specialization.
+///
+/// # Safety
+///
+/// Implementors must have the same size, alignment and bit validity as the corresponding
+/// C++ element type, and `RawArray` must be the matching `VtArray` specialization.
+/// [`Array::as_slice`] and [`Array::as_mut_slice`] reinterpret the array's storage as
+/// `[Self]`.
+///
+/// The raw methods are only called by [`Array`] and [`ArrayRef`], always with pointers to
+/// live `RawArray`s and `VtValue`s.
+#[allow(clippy::missing_safety_doc)]
+pub unsafe trait ArrayElement: Sized + Copy {
+    type RawArray;
+
+    unsafe fn array_default() -> *mut Self::RawArray;
+    unsafe fn array_from_slice(values: &[Self]) -> *mut Self::RawArray;
+    unsafe fn array_copy(ptr: *mut Self::RawArray) -> *mut Self::RawArray;
+    unsafe fn array_dtor(ptr: *mut Self::RawArray);
+    unsafe fn array_size(ptr: *mut Self::RawArray) -> usize;
+    unsafe fn array_capacity(ptr: *mut Self::RawArray) -> usize;
+    unsafe fn array_cdata(ptr: *mut Self::RawArray) -> *const Self;
+    unsafe fn array_data(ptr: *mut Self::RawArray) -> *mut Self;
+    unsafe fn array_push_back(ptr: *mut Self::RawArray, value: &Self);
+    unsafe fn array_resize(ptr: *mut Self::RawArray, len: usize);
+    unsafe fn array_reserve(ptr: *mut Self::RawArray, capacity: usize);
+    unsafe fn array_clear(ptr: *mut Self::RawArray);
+
+    unsafe fn value_is_holding(value: *mut ffi::vt_Value_t) -> bool;
+    unsafe fn value_get(value: *mut ffi::vt_Value_t) -> *const Self::RawArray;
+    unsafe fn value_from(ptr: *mut Self::RawArray) -> *mut ffi::vt_Value_t;
+    unsafe fn value_type() -> *mut ffi::tf_Type_t;
+
+    unsafe fn attribute_get(
+        attr: *mut ffi::usd_Attribute_t,
+        ptr: *mut Self::RawArray,
+        time: usd::TimeCode,
+    ) -> bool;
+    unsafe fn attribute_set(
+        attr: *mut ffi::usd_Attribute_t,
+        ptr: *mut Self::RawArray,
+        time: usd::TimeCode,
+    ) -> bool;
+}
+
+macro_rules! array_element {
+    ($elem:ty, $ffi_elem:ty, $ffi:ident, $cpp:ident) => {
+        paste! {
+            unsafe impl ArrayElement for $elem {
+                type RawArray = ffi::[<$ffi _t>];
+
+                unsafe fn array_default() -> *mut Self::RawArray {
+                    let mut ptr = std::ptr::null_mut();
+                    ffi::[<$ffi _default>](&mut ptr);
+                    ptr
+                }
+
+                unsafe fn array_from_slice(values: &[Self]) -> *mut Self::RawArray {
+                    let range = values.as_ptr_range();
+                    let mut ptr = std::ptr::null_mut();
+                    ffi::[<$ffi _from_range>](
+                        range.start as *const $ffi_elem,
+                        range.end as *const $ffi_elem,
+                        &mut ptr,
+                    );
+                    ptr
+                }
+
+                unsafe fn array_copy(ptr: *mut Self::RawArray) -> *mut Self::RawArray {
+                    let mut result = std::ptr::null_mut();
+                    ffi::[<$ffi _copy>](ptr, &mut result);
+                    result
+                }
+
+                unsafe fn array_dtor(ptr: *mut Self::RawArray) {
+                    ffi::[<$ffi _dtor>](ptr);
+                }
+
+                unsafe fn array_size(ptr: *mut Self::RawArray) -> usize {
+                    let mut result = 0;
+                    ffi::[<$ffi _size>](ptr, &mut result);
+                    result
+                }
+
+                unsafe fn array_capacity(ptr: *mut Self::RawArray) -> usize {
+                    let mut result = 0;
+                    ffi::[<$ffi _capacity>](ptr, &mut result);
+                    result
+                }
+
+                unsafe fn array_cdata(ptr: *mut Self::RawArray) -> *const Self {
+                    let mut result = std::ptr::null();
+                    ffi::[<$ffi _cdata>](ptr, &mut result);
+                    result as *const Self
+                }
+
+                unsafe fn array_data(ptr: *mut Self::RawArray) -> *mut Self {
+                    let mut result = std::ptr::null_mut();
+                    ffi::[<$ffi _data>](ptr, &mut result);
+                    result as *mut Self
+                }
+
+                unsafe fn array_push_back(ptr: *mut Self::RawArray, value: &Self) {
+                    ffi::[<$ffi _push_back>](ptr, value as *const Self as *const $ffi_elem);
+                }
+
+                unsafe fn array_resize(ptr: *mut Self::RawArray, len: usize) {
+                    ffi::[<$ffi _resize>](ptr, len);
+                }
+
+                unsafe fn array_reserve(ptr: *mut Self::RawArray, capacity: usize) {
+                    ffi::[<$ffi _reserve>](ptr, capacity);
+                }
+
+                unsafe fn array_clear(ptr: *mut Self::RawArray) {
+                    ffi::[<$ffi _clear>](ptr);
+                }
+
+                unsafe fn value_is_holding(value: *mut ffi::vt_Value_t) -> bool {
+                    let mut result = false;
+                    ffi::[<vt_Value_IsHolding_ $cpp>](value, &mut result);
+                    result
+                }
+
+                unsafe fn value_get(value: *mut ffi::vt_Value_t) -> *const Self::RawArray {
+                    let mut ptr = std::ptr::null();
+                    ffi::[<vt_Value_Get_ $cpp>](value, &mut ptr);
+                    ptr
+                }
+
+                unsafe fn value_from(ptr: *mut Self::RawArray) -> *mut ffi::vt_Value_t {
+                    let mut result = std::ptr::null_mut();
+                    ffi::[<vt_Value_from_ $cpp>](ptr, &mut result);
+                    result
+                }
+
+                unsafe fn value_type() -> *mut ffi::tf_Type_t {
+                    let mut result = std::ptr::null_mut();
+                    ffi::[<tf_Type_Find_ $cpp>](&mut result);
+                    result
+                }
+
+                unsafe fn attribute_get(
+                    attr: *mut ffi::usd_Attribute_t,
+                    ptr: *mut Self::RawArray,
+                    time: usd::TimeCode,
+                ) -> bool {
+                    let mut result = false;
+                    ffi::[<usd_Attribute_Get_ $cpp>](attr, ptr, time.0, &mut result);
+                    result
+                }
+
+                unsafe fn attribute_set(
+                    attr: *mut ffi::usd_Attribute_t,
+                    ptr: *mut Self::RawArray,
+                    time: usd::TimeCode,
+                ) -> bool {
+                    let mut result = false;
+                    ffi::[<usd_Attribute_Set_ $cpp>](attr, ptr, time.0, &mut result);
+                    result
+                }
+            }
+        }
+    };
+}
+
+array_element!(bool, bool, vt_BoolArray, VtBoolArray);
+array_element!(u8, u8, vt_UCharArray, VtUCharArray);
+array_element!(i32, i32, vt_IntArray, VtIntArray);
+array_element!(u32, u32, vt_UIntArray, VtUIntArray);
+array_element!(i64, i64, vt_Int64Array, VtInt64Array);
+array_element!(f16, ffi::gf_Half_t, vt_HalfArray, VtHalfArray);
+array_element!(f32, f32, vt_FloatArray, VtFloatArray);
+array_element!(f64, f64, vt_DoubleArray, VtDoubleArray);
+array_element!(sdf::TimeCode, ffi::sdf_TimeCode_t, sdf_TimeCodeArray, VtSdfTimeCodeArray);
+array_element!(Vec2, ffi::gf_Vec2f_t, gf_Vec2fArray, VtVec2fArray);
+array_element!(Vec3, ffi::gf_Vec3f_t, gf_Vec3fArray, VtVec3fArray);
+array_element!(Vec4, ffi::gf_Vec4f_t, gf_Vec4fArray, VtVec4fArray);
+array_element!(DVec2, ffi::gf_Vec2d_t, gf_Vec2dArray, VtVec2dArray);
+array_element!(DVec3, ffi::gf_Vec3d_t, gf_Vec3dArray, VtVec3dArray);
+array_element!(DVec4, ffi::gf_Vec4d_t, gf_Vec4dArray, VtVec4dArray);
+array_element!(IVec2, ffi::gf_Vec2i_t, gf_Vec2iArray, VtVec2iArray);
+array_element!(IVec3, ffi::gf_Vec3i_t, gf_Vec3iArray, VtVec3iArray);
+array_element!(IVec4, ffi::gf_Vec4i_t, gf_Vec4iArray, VtVec4iArray);
+array_element!(DMat2, ffi::gf_Matrix2d_t, gf_Matrix2dArray, VtMatrix2dArray);
+array_element!(DMat3, ffi::gf_Matrix3d_t, gf_Matrix3dArray, VtMatrix3dArray);
+array_element!(DMat4, ffi::gf_Matrix4d_t, gf_Matrix4dArray, VtMatrix4dArray);
+array_element!(Quat, ffi::gf_Quatf_t, gf_QuatfArray, VtQuatfArray);
+array_element!(DQuat, ffi::gf_Quatd_t, gf_QuatdArray, VtQuatdArray);
+array_element!(Quath, ffi::gf_Quath_t, gf_QuathArray, VtQuathArray);
+
+pub type BoolArray = Array<bool>;
+pub type UCharArray = Array<u8>;
+pub type IntArray = Array<i32>;
+pub type UIntArray = Array<u32>;
+pub type Int64Array = Array<i64>;
+pub type HalfArray = Array<f16>;
+pub type FloatArray = Array<f32>;
+pub type DoubleArray = Array<f64>;
+pub type TimeCodeArray = Array<sdf::TimeCode>;
+pub type Vec2Array = Array<Vec2>;
+pub type Vec3Array = Array<Vec3>;
+pub type Vec4Array = Array<Vec4>;
+pub type DVec2Array = Array<DVec2>;
+pub type DVec3Array = Array<DVec3>;
+pub type DVec4Array = Array<DVec4>;
+pub type IVec2Array = Array<IVec2>;
+pub type IVec3Array = Array<IVec3>;
+pub type IVec4Array = Array<IVec4>;
+pub type DMat2Array = Array<DMat2>;
+pub type DMat3Array = Array<DMat3>;
+pub type DMat4Array = Array<DMat4>;
+pub type QuatArray = Array<Quat>;
+pub type DQuatArray = Array<DQuat>;
+pub type QuathArray = Array<Quath>;
+
+pub type IntArrayRef<'a> = ArrayRef<'a, i32>;
+pub type FloatArrayRef<'a> = ArrayRef<'a, f32>;
+pub type DoubleArrayRef<'a> = ArrayRef<'a, f64>;
+pub type Vec2ArrayRef<'a> = ArrayRef<'a, Vec2>;
+pub type Vec3ArrayRef<'a> = ArrayRef<'a, Vec3>;
+pub type Vec4ArrayRef<'a> = ArrayRef<'a, Vec4>;
 
-pub struct Vec3ArrayIterator<'a> {
-    vec: &'a Vec3Array,
-    current: usize,
-    end: usize,
+pub struct Value {
+    pub(crate) ptr: *mut ffi::vt_Value_t,
 }
 
-impl<'a> Iterator for Vec3ArrayIterator<'a> {
-    type Item = &'a Vec3;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
-        } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
+impl Value {
+    pub fn new() -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Value_new(&mut ptr);
+            Value { ptr }
         }
     }
-}
-
-pub struct Vec3ArrayRef {
-    pub(crate) ptr: *const ffi::gf_Vec3fArray_t,
-}
 
-impl std::ops::Deref for Vec3ArrayRef {
-    type Target = Vec3Array;
+    /// The name of the held C++ type, e.g. `"VtArray<GfVec3f>"`.
+    pub fn type_name(&self) -> String {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Value_GetTypeName(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            let result = CStr::from_ptr(ptr_c_str).to_string_lossy().to_string();
+            ffi::std_String_dtor(ptr);
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const Vec3ArrayRef as *const Vec3Array) }
+            result
+        }
     }
-}
-
-pub struct Vec4Array {
-    pub(crate) ptr: *mut ffi::gf_Vec4fArray_t,
-}
 
-impl Vec4Array {
-    pub fn size(&self) -> usize {
+    pub fn is_empty(&self) -> bool {
         unsafe {
-            let mut result = 0;
-            ffi::gf_Vec4fArray_size(self.ptr, &mut result);
+            let mut result = false;
+            ffi::vt_Value_IsEmpty(self.ptr, &mut result);
             result
         }
     }
 
-    pub fn at(&self, index: usize) -> &Vec4 {
+    pub fn is_array_valued(&self) -> bool {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::gf_Vec4fArray_op_index(self.ptr, index, &mut ptr);
-            &*(ptr as *mut Vec4)
+            let mut result = false;
+            ffi::vt_Value_IsArrayValued(self.ptr, &mut result);
+            result
         }
     }
 
-    pub fn iter(&self) -> Vec4ArrayIterator {
-        Vec4ArrayIterator {
-            vec: self,
-            current: 0,
-            end: self.size(),
+    /// The number of elements if the value holds a `VtArray`, otherwise 0.
+    pub fn array_size(&self) -> usize {
+        unsafe {
+            let mut result = 0;
+            ffi::vt_Value_GetArraySize(self.ptr, &mut result);
+            result
         }
     }
-}
-
-impl<'a> IntoIterator for &'a Vec4Array {
-    type Item = &'a Vec4;
-    type IntoIter = Vec4ArrayIterator<'a>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
-    }
-}
-
-pub struct Vec4ArrayIterator<'a> {
-    vec: &'a Vec4Array,
-    current: usize,
-    end: usize,
-}
 
-impl<'a> Iterator for Vec4ArrayIterator<'a> {
-    type Item = &'a Vec4;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.end {
-            None
+    /// A view of the held value that can be matched exhaustively.
+    pub fn kind(&self) -> ValueKind<'_> {
+        macro_rules! probe_copied {
+            ($($variant:ident => $ty:ty),* $(,)?) => {
+                $(
+                    if let Some(v) = self.get::<$ty>() {
+                        return ValueKind::$variant(*v);
+                    }
+                )*
+            };
+        }
+
+        macro_rules! probe {
+            ($($variant:ident => $ty:ty),* $(,)?) => {
+                $(
+                    if let Some(v) = self.get::<$ty>() {
+                        return ValueKind::$variant(v);
+                    }
+                )*
+            };
+        }
+
+        if self.is_empty() {
+            return ValueKind::Empty;
+        }
+
+        if self.is_array_valued() {
+            probe!(
+                TokenArray => TokenArray,
+                StringArray => StringArray,
+                BoolArray => BoolArray,
+                UCharArray => UCharArray,
+                IntArray => IntArray,
+                UIntArray => UIntArray,
+                Int64Array => Int64Array,
+                HalfArray => HalfArray,
+                FloatArray => FloatArray,
+                DoubleArray => DoubleArray,
+                TimeCodeArray => TimeCodeArray,
+                Float2Array => Vec2Array,
+                Float3Array => Vec3Array,
+                Float4Array => Vec4Array,
+                Double2Array => DVec2Array,
+                Double3Array => DVec3Array,
+                Double4Array => DVec4Array,
+                Int2Array => IVec2Array,
+                Int3Array => IVec3Array,
+                Int4Array => IVec4Array,
+                Matrix2dArray => DMat2Array,
+                Matrix3dArray => DMat3Array,
+                Matrix4dArray => DMat4Array,
+                QuatfArray => QuatArray,
+                QuatdArray => DQuatArray,
+                QuathArray => QuathArray,
+            );
         } else {
-            let cur = self.current;
-            self.current += 1;
-            Some(self.vec.at(cur))
+            probe!(
+                Token => tf::Token,
+                String => String,
+                AssetPath => sdf::AssetPath,
+                Dictionary => Dictionary,
+            );
+            probe_copied!(
+                Bool => bool,
+                UChar => u8,
+                Int => i32,
+                UInt => u32,
+                Int64 => i64,
+                Half => f16,
+                Float => f32,
+                Double => f64,
+                TimeCode => sdf::TimeCode,
+                Float2 => Vec2,
+                Float3 => Vec3,
+                Float4 => Vec4,
+                Double2 => DVec2,
+                Double3 => DVec3,
+                Double4 => DVec4,
+                Int2 => IVec2,
+                Int3 => IVec3,
+                Int4 => IVec4,
+                Matrix2d => DMat2,
+                Matrix3d => DMat3,
+                Matrix4d => DMat4,
+                Quatf => Quat,
+                Quatd => DQuat,
+                Quath => Quath,
+            );
         }
-    }
-}
-
-pub struct Vec4ArrayRef {
-    pub(crate) ptr: *const ffi::gf_Vec4fArray_t,
-}
 
-impl std::ops::Deref for Vec4ArrayRef {
-    type Target = Vec4Array;
+        ValueKind::Unknown(self.type_name())
+    }
 
-    fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const Vec4ArrayRef as *const Vec4Array) }
+    pub fn get<T: ValueMember>(&self) -> Option<T::Ref<'_>> {
+        T::get(self)
     }
-}
 
-pub struct Value {
-    pub(crate) ptr: *mut ffi::vt_Value_t,
-}
+    pub fn is_holding<T: ValueMember>(&self) -> bool {
+        T::is_holding(self)
+    }
 
-impl Value {
-    pub fn get<T: ValueMember>(&self) -> Option<&T> {
-        T::get(self)
+    pub fn from_member<T: ValueMember>(member: &T) -> Value {
+        T::as_value(member)
     }
 }
 
 impl Value {
-    pub fn as_token(&self) -> Option<tf::TokenRef> {
+    pub fn as_token(&self) -> Option<tf::TokenRef<'_>> {
         unsafe {
             let mut is_holding = false;
             ffi::vt_Value_IsHolding_TfToken(self.ptr, &mut is_holding);
             if is_holding {
                 let mut ptr = std::ptr::null();
                 ffi::vt_Value_Get_TfToken(self.ptr, &mut ptr);
-                Some(tf::TokenRef { ptr })
+                Some(tf::TokenRef::from_ptr(ptr))
             } else {
                 None
             }
         }
     }
 
-    pub fn as_token_array(&self) -> Option<TokenArrayRef> {
+    pub fn as_token_array(&self) -> Option<TokenArrayRef<'_>> {
         unsafe {
             let mut is_holding = false;
             ffi::vt_Value_IsHolding_VtTokenArray(self.ptr, &mut is_holding);
             if is_holding {
                 let mut ptr = std::ptr::null();
                 ffi::vt_Value_Get_VtTokenArray(self.ptr, &mut ptr);
-                Some(TokenArrayRef { ptr })
+                Some(TokenArrayRef::from_ptr(ptr))
             } else {
                 None
             }
         }
     }
 
-    pub fn as_int_array(&self) -> Option<IntArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtIntArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtIntArray(self.ptr, &mut ptr);
-                Some(IntArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_int_array(&self) -> Option<IntArrayRef<'_>> {
+        self.get::<IntArray>()
     }
 
-    pub fn as_float_array(&self) -> Option<FloatArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtFloatArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtFloatArray(self.ptr, &mut ptr);
-                Some(FloatArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_float_array(&self) -> Option<FloatArrayRef<'_>> {
+        self.get::<FloatArray>()
     }
 
-    pub fn as_double_array(&self) -> Option<DoubleArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtDoubleArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtDoubleArray(self.ptr, &mut ptr);
-                Some(DoubleArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_double_array(&self) -> Option<DoubleArrayRef<'_>> {
+        self.get::<DoubleArray>()
     }
 
-    pub fn as_vec2_array(&self) -> Option<Vec2ArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtVec2fArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtVec2fArray(self.ptr, &mut ptr);
-                Some(Vec2ArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_vec2_array(&self) -> Option<Vec2ArrayRef<'_>> {
+        self.get::<Vec2Array>()
     }
 
-    pub fn as_vec3_array(&self) -> Option<Vec3ArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtVec3fArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtVec3fArray(self.ptr, &mut ptr);
-                Some(Vec3ArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_vec3_array(&self) -> Option<Vec3ArrayRef<'_>> {
+        self.get::<Vec3Array>()
     }
 
-    pub fn as_vec4_array(&self) -> Option<Vec4ArrayRef> {
-        unsafe {
-            let mut is_holding = false;
-            ffi::vt_Value_IsHolding_VtVec4fArray(self.ptr, &mut is_holding);
-            if is_holding {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtVec4fArray(self.ptr, &mut ptr);
-                Some(Vec4ArrayRef { ptr })
-            } else {
-                None
-            }
-        }
+    pub fn as_vec4_array(&self) -> Option<Vec4ArrayRef<'_>> {
+        self.get::<Vec4Array>()
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        self.get::<String>()
+    }
+
+    pub fn as_string_array(&self) -> Option<StringArrayRef<'_>> {
+        self.get::<StringArray>()
     }
 
-    pub fn as_asset_path(&self) -> Option<sdf::AssetPathRef> {
+    pub fn as_asset_path(&self) -> Option<sdf::AssetPathRef<'_>> {
         unsafe {
             let mut is_holding = false;
             ffi::vt_Value_IsHolding_SdfAssetPath(self.ptr, &mut is_holding);
             if is_holding {
                 let mut ptr = std::ptr::null();
                 ffi::vt_Value_Get_SdfAssetPath(self.ptr, &mut ptr);
-                Some(sdf::AssetPathRef { ptr })
+                Some(sdf::AssetPathRef::from_ptr(ptr))
             } else {
                 None
             }
@@ -660,11 +949,88 @@ impl Value {
     }
 }
 
-pub struct ValueRef {
+/// The contents of a [`Value`], as returned by [`Value::kind`].
+///
+/// Variants are named after the corresponding `sdf::ValueTypeName`. Values of types that are
+/// not bound here are reported as `Unknown` with the C++ type name.
+pub enum ValueKind<'a> {
+    Empty,
+    Bool(bool),
+    UChar(u8),
+    Int(i32),
+    UInt(u32),
+    Int64(i64),
+    Half(f16),
+    Float(f32),
+    Double(f64),
+    TimeCode(sdf::TimeCode),
+    String(&'a str),
+    Token(tf::TokenRef<'a>),
+    AssetPath(sdf::AssetPathRef<'a>),
+    Dictionary(DictionaryRef<'a>),
+    Float2(Vec2),
+    Float3(Vec3),
+    Float4(Vec4),
+    Double2(DVec2),
+    Double3(DVec3),
+    Double4(DVec4),
+    Int2(IVec2),
+    Int3(IVec3),
+    Int4(IVec4),
+    Matrix2d(DMat2),
+    Matrix3d(DMat3),
+    Matrix4d(DMat4),
+    Quatf(Quat),
+    Quatd(DQuat),
+    Quath(Quath),
+    BoolArray(ArrayRef<'a, bool>),
+    UCharArray(ArrayRef<'a, u8>),
+    IntArray(ArrayRef<'a, i32>),
+    UIntArray(ArrayRef<'a, u32>),
+    Int64Array(ArrayRef<'a, i64>),
+    HalfArray(ArrayRef<'a, f16>),
+    FloatArray(ArrayRef<'a, f32>),
+    DoubleArray(ArrayRef<'a, f64>),
+    TimeCodeArray(ArrayRef<'a, sdf::TimeCode>),
+    StringArray(StringArrayRef<'a>),
+    TokenArray(TokenArrayRef<'a>),
+    Float2Array(ArrayRef<'a, Vec2>),
+    Float3Array(ArrayRef<'a, Vec3>),
+    Float4Array(ArrayRef<'a, Vec4>),
+    Double2Array(ArrayRef<'a, DVec2>),
+    Double3Array(ArrayRef<'a, DVec3>),
+    Double4Array(ArrayRef<'a, DVec4>),
+    Int2Array(ArrayRef<'a, IVec2>),
+    Int3Array(ArrayRef<'a, IVec3>),
+    Int4Array(ArrayRef<'a, IVec4>),
+    Matrix2dArray(ArrayRef<'a, DMat2>),
+    Matrix3dArray(ArrayRef<'a, DMat3>),
+    Matrix4dArray(ArrayRef<'a, DMat4>),
+    QuatfArray(ArrayRef<'a, Quat>),
+    QuatdArray(ArrayRef<'a, DQuat>),
+    QuathArray(ArrayRef<'a, Quath>),
+    Unknown(String),
+}
+
+/// A borrowed [`Value`] held by another object, such as a [`Dictionary`].
+#[repr(transparent)]
+pub struct ValueRef<'a> {
     pub(crate) ptr: *mut ffi::vt_Value_t,
+    _marker: PhantomData<&'a Value>,
+}
+
+impl<'a> ValueRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid value that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::vt_Value_t) -> Self {
+        ValueRef {
+            ptr: ptr as *mut ffi::vt_Value_t,
+            _marker: PhantomData,
+        }
+    }
 }
 
-impl std::ops::Deref for ValueRef {
+impl<'a> std::ops::Deref for ValueRef<'a> {
     type Target = Value;
 
     fn deref(&self) -> &Self::Target {
@@ -672,6 +1038,57 @@ impl std::ops::Deref for ValueRef {
     }
 }
 
+impl Default for Value {
+    fn default() -> Self {
+        Value::new()
+    }
+}
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Value_copy(self.ptr, &mut ptr);
+            Value { ptr }
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::vt_Value_op_eq(self.ptr, other.ptr, &mut result);
+            result
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Stringify_VtValue(self.ptr, &mut ptr);
+            let mut ptr_c_str = std::ptr::null();
+            ffi::std_String_c_str(ptr, &mut ptr_c_str);
+            let result = write!(f, "{}", CStr::from_ptr(ptr_c_str).to_string_lossy());
+            ffi::std_String_dtor(ptr);
+
+            result
+        }
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            write!(f, "Value(<empty>)")
+        } else {
+            write!(f, "Value({}: {})", self.type_name(), self)
+        }
+    }
+}
+
 impl Drop for Value {
     fn drop(&mut self) {
         unsafe {
@@ -681,18 +1098,145 @@ impl Drop for Value {
 }
 
 pub trait ValueMember {
-    fn get(value: &Value) -> Option<&Self>;
+    type Ref<'a>
+    where
+        Self: 'a;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>>;
     fn is_holding(value: &Value) -> bool;
     fn as_value(member: &Self) -> Value;
-}
 
-impl ValueMember for i32 {
-    fn get(value: &Value) -> Option<&Self> {
+    /// The C++ type a `VtValue` holding this type reports, looked up without creating a value.
+    fn tf_type() -> tf::Type;
+
+    /// Read `attr` at `time` directly through the typed `UsdAttribute::Get<T>`
+    /// overload, without going through a `VtValue`.
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Write `member` to `attr` at `time` through the typed `UsdAttribute::Set<T>` overload.
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool;
+}
+
+/// Generates a `ValueMember` impl for a plain-old-data type whose memory layout matches the
+/// C++ type `$cpp`.
+macro_rules! pod_value_member {
+    ($ty:ty, $ffi_ty:ty, $cpp:ident) => {
+        paste! {
+            impl ValueMember for $ty {
+                type Ref<'a> = &'a $ty;
+
+                fn get(value: &Value) -> Option<Self::Ref<'_>> {
+                    if Self::is_holding(value) {
+                        unsafe {
+                            let mut ptr = std::ptr::null();
+                            ffi::[<vt_Value_Get_ $cpp>](value.ptr, &mut ptr);
+                            Some(&*(ptr as *const $ty))
+                        }
+                    } else {
+                        None
+                    }
+                }
+
+                fn is_holding(value: &Value) -> bool {
+                    unsafe {
+                        let mut result = false;
+                        ffi::[<vt_Value_IsHolding_ $cpp>](value.ptr, &mut result);
+                        result
+                    }
+                }
+
+                fn as_value(member: &Self) -> Value {
+                    unsafe {
+                        let mut ptr = std::ptr::null_mut();
+                        ffi::[<vt_Value_from_ $cpp>](
+                            *(member as *const $ty as *const $ffi_ty),
+                            &mut ptr,
+                        );
+                        Value { ptr }
+                    }
+                }
+
+                fn tf_type() -> tf::Type {
+                    unsafe {
+                        let mut ptr = std::ptr::null_mut();
+                        ffi::[<tf_Type_Find_ $cpp>](&mut ptr);
+                        tf::Type { ptr }
+                    }
+                }
+
+                fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+                    unsafe {
+                        let mut value = <$ty>::default();
+                        let mut result = false;
+                        ffi::[<usd_Attribute_Get_ $cpp>](
+                            attr.ptr,
+                            &mut value as *mut $ty as *mut $ffi_ty,
+                            time.0,
+                            &mut result,
+                        );
+                        if result {
+                            Some(value)
+                        } else {
+                            None
+                        }
+                    }
+                }
+
+                fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
+                    unsafe {
+                        let mut result = false;
+                        ffi::[<usd_Attribute_Set_ $cpp>](
+                            attr.ptr,
+                            member as *const $ty as *const $ffi_ty,
+                            time.0,
+                            &mut result,
+                        );
+                        result
+                    }
+                }
+            }
+        }
+    };
+}
+
+pod_value_member!(bool, bool, bool);
+pod_value_member!(u8, u8, uchar);
+pod_value_member!(i32, i32, int);
+pod_value_member!(u32, u32, uint);
+pod_value_member!(i64, i64, int64);
+pod_value_member!(f16, ffi::gf_Half_t, GfHalf);
+pod_value_member!(f32, f32, float);
+pod_value_member!(f64, f64, double);
+pod_value_member!(sdf::TimeCode, ffi::sdf_TimeCode_t, SdfTimeCode);
+pod_value_member!(Vec2, ffi::gf_Vec2f_t, GfVec2f);
+pod_value_member!(Vec3, ffi::gf_Vec3f_t, GfVec3f);
+pod_value_member!(Vec4, ffi::gf_Vec4f_t, GfVec4f);
+pod_value_member!(DVec2, ffi::gf_Vec2d_t, GfVec2d);
+pod_value_member!(DVec3, ffi::gf_Vec3d_t, GfVec3d);
+pod_value_member!(DVec4, ffi::gf_Vec4d_t, GfVec4d);
+pod_value_member!(IVec2, ffi::gf_Vec2i_t, GfVec2i);
+pod_value_member!(IVec3, ffi::gf_Vec3i_t, GfVec3i);
+pod_value_member!(IVec4, ffi::gf_Vec4i_t, GfVec4i);
+pod_value_member!(DMat2, ffi::gf_Matrix2d_t, GfMatrix2d);
+pod_value_member!(DMat3, ffi::gf_Matrix3d_t, GfMatrix3d);
+pod_value_member!(DMat4, ffi::gf_Matrix4d_t, GfMatrix4d);
+pod_value_member!(Quat, ffi::gf_Quatf_t, GfQuatf);
+pod_value_member!(DQuat, ffi::gf_Quatd_t, GfQuatd);
+pod_value_member!(Quath, ffi::gf_Quath_t, GfQuath);
+
+impl ValueMember for String {
+    type Ref<'a> = &'a str;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_int(value.ptr, &mut ptr);
-                Some(&*ptr)
+                ffi::vt_Value_Get_string(value.ptr, &mut ptr);
+                let mut ptr_c_str = std::ptr::null();
+                ffi::std_String_c_str(ptr, &mut ptr_c_str);
+                Some(CStr::from_ptr(ptr_c_str).to_str().unwrap())
             }
         } else {
             None
@@ -702,57 +1246,62 @@ impl ValueMember for i32 {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_int(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_string(value.ptr, &mut result);
             result
         }
     }
 
     fn as_value(member: &Self) -> Value {
         unsafe {
+            let string = cpp::String::new(member);
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_int(*member, &mut ptr);
+            ffi::vt_Value_from_string(string.ptr, &mut ptr);
             Value { ptr }
         }
     }
-}
 
-impl ValueMember for IntArray {
-    fn get(value: &Value) -> Option<&Self> {
-        if Self::is_holding(value) {
-            unsafe {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtIntArray(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut IntArray))
-            }
-        } else {
-            None
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_string(&mut ptr);
+            tf::Type { ptr }
         }
     }
 
-    fn is_holding(value: &Value) -> bool {
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
         unsafe {
+            let string = cpp::String::new("");
             let mut result = false;
-            ffi::vt_Value_IsHolding_VtIntArray(value.ptr, &mut result);
-            result
+            ffi::usd_Attribute_Get_string(attr.ptr, string.ptr, time.0, &mut result);
+            if result {
+                let mut ptr_c_str = std::ptr::null();
+                ffi::std_String_c_str(string.ptr, &mut ptr_c_str);
+                Some(CStr::from_ptr(ptr_c_str).to_string_lossy().to_string())
+            } else {
+                None
+            }
         }
     }
 
-    fn as_value(member: &Self) -> Value {
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
         unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_VtIntArray(member.ptr, &mut ptr);
-            Value { ptr }
+            let string = cpp::String::new(member);
+            let mut result = false;
+            ffi::usd_Attribute_Set_string(attr.ptr, string.ptr, time.0, &mut result);
+            result
         }
     }
 }
 
-impl ValueMember for f32 {
-    fn get(value: &Value) -> Option<&Self> {
+impl ValueMember for tf::Token {
+    type Ref<'a> = tf::TokenRef<'a>;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_float(value.ptr, &mut ptr);
-                Some(&*ptr)
+                ffi::vt_Value_Get_TfToken(value.ptr, &mut ptr);
+                Some(tf::TokenRef::from_ptr(ptr))
             }
         } else {
             None
@@ -762,7 +1311,7 @@ impl ValueMember for f32 {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_float(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_TfToken(value.ptr, &mut result);
             result
         }
     }
@@ -770,19 +1319,52 @@ impl ValueMember for f32 {
     fn as_value(member: &Self) -> Value {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_float(*member, &mut ptr);
+            ffi::vt_Value_from_TfToken(member.ptr, &mut ptr);
             Value { ptr }
         }
     }
+
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_TfToken(&mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Token_default(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get_TfToken(attr.ptr, ptr, time.0, &mut result);
+            if result {
+                Some(tf::Token { ptr })
+            } else {
+                ffi::tf_Token_dtor(ptr);
+                None
+            }
+        }
+    }
+
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_Set_TfToken(attr.ptr, member.ptr, time.0, &mut result);
+            result
+        }
+    }
 }
 
-impl ValueMember for f64 {
-    fn get(value: &Value) -> Option<&Self> {
+impl ValueMember for TokenArray {
+    type Ref<'a> = TokenArrayRef<'a>;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_double(value.ptr, &mut ptr);
-                Some(&*ptr)
+                ffi::vt_Value_Get_VtTokenArray(value.ptr, &mut ptr);
+                Some(TokenArrayRef::from_ptr(ptr))
             }
         } else {
             None
@@ -792,7 +1374,7 @@ impl ValueMember for f64 {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_double(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_VtTokenArray(value.ptr, &mut result);
             result
         }
     }
@@ -800,19 +1382,52 @@ impl ValueMember for f64 {
     fn as_value(member: &Self) -> Value {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_double(*member, &mut ptr);
+            ffi::vt_Value_from_VtTokenArray(member.ptr, &mut ptr);
             Value { ptr }
         }
     }
+
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_VtTokenArray(&mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_TokenArray_default(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get_VtTokenArray(attr.ptr, ptr, time.0, &mut result);
+            if result {
+                Some(TokenArray { ptr })
+            } else {
+                ffi::vt_TokenArray_dtor(ptr);
+                None
+            }
+        }
+    }
+
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_Set_VtTokenArray(attr.ptr, member.ptr, time.0, &mut result);
+            result
+        }
+    }
 }
 
-impl ValueMember for bool {
-    fn get(value: &Value) -> Option<&Self> {
+impl ValueMember for StringArray {
+    type Ref<'a> = StringArrayRef<'a>;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_bool(value.ptr, &mut ptr);
-                Some(&*ptr)
+                ffi::vt_Value_Get_VtStringArray(value.ptr, &mut ptr);
+                Some(StringArrayRef::from_ptr(ptr))
             }
         } else {
             None
@@ -822,7 +1437,7 @@ impl ValueMember for bool {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_bool(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_VtStringArray(value.ptr, &mut result);
             result
         }
     }
@@ -830,19 +1445,52 @@ impl ValueMember for bool {
     fn as_value(member: &Self) -> Value {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_bool(*member, &mut ptr);
+            ffi::vt_Value_from_VtStringArray(member.ptr, &mut ptr);
             Value { ptr }
         }
     }
+
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_VtStringArray(&mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_StringArray_default(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get_VtStringArray(attr.ptr, ptr, time.0, &mut result);
+            if result {
+                Some(StringArray { ptr })
+            } else {
+                ffi::vt_StringArray_dtor(ptr);
+                None
+            }
+        }
+    }
+
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
+        unsafe {
+            let mut result = false;
+            ffi::usd_Attribute_Set_VtStringArray(attr.ptr, member.ptr, time.0, &mut result);
+            result
+        }
+    }
 }
 
-impl ValueMember for Vec2 {
-    fn get(value: &Value) -> Option<&Self> {
+impl ValueMember for sdf::AssetPath {
+    type Ref<'a> = sdf::AssetPathRef<'a>;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_GfVec2f(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut Vec2))
+                ffi::vt_Value_Get_SdfAssetPath(value.ptr, &mut ptr);
+                Some(sdf::AssetPathRef::from_ptr(ptr))
             }
         } else {
             None
@@ -852,7 +1500,7 @@ impl ValueMember for Vec2 {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_GfVec2f(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_SdfAssetPath(value.ptr, &mut result);
             result
         }
     }
@@ -860,85 +1508,192 @@ impl ValueMember for Vec2 {
     fn as_value(member: &Self) -> Value {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_GfVec2f(
-                *(member as *const Vec2 as *const ffi::gf_Vec2f_t),
-                &mut ptr,
-            );
+            ffi::vt_Value_from_SdfAssetPath(member.ptr, &mut ptr);
             Value { ptr }
         }
     }
-}
 
-impl ValueMember for Vec3 {
-    fn get(value: &Value) -> Option<&Self> {
-        if Self::is_holding(value) {
-            unsafe {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_GfVec3f(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut Vec3))
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_SdfAssetPath(&mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::sdf_AssetPath_default(&mut ptr);
+            let mut result = false;
+            ffi::usd_Attribute_Get_SdfAssetPath(attr.ptr, ptr, time.0, &mut result);
+            if result {
+                Some(sdf::AssetPath { ptr })
+            } else {
+                ffi::sdf_AssetPath_dtor(ptr);
+                None
             }
-        } else {
-            None
         }
     }
 
-    fn is_holding(value: &Value) -> bool {
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_GfVec3f(value.ptr, &mut result);
+            ffi::usd_Attribute_Set_SdfAssetPath(attr.ptr, member.ptr, time.0, &mut result);
             result
         }
     }
+}
 
-    fn as_value(member: &Self) -> Value {
+/// A `VtDictionary`: a map from string keys to [`Value`]s, used for metadata such as
+/// `customData` and `customLayerData`.
+pub struct Dictionary {
+    pub(crate) ptr: *mut ffi::vt_Dictionary_t,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_GfVec3f(
-                *(member as *const Vec3 as *const ffi::gf_Vec3f_t),
-                &mut ptr,
-            );
-            Value { ptr }
+            ffi::vt_Dictionary_default(&mut ptr);
+            Dictionary { ptr }
         }
     }
-}
 
-impl ValueMember for Vec3Array {
-    fn get(value: &Value) -> Option<&Self> {
-        if Self::is_holding(value) {
-            unsafe {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_VtVec3fArray(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut Vec3Array))
+    pub fn len(&self) -> usize {
+        unsafe {
+            let mut result = 0;
+            ffi::vt_Dictionary_size(self.ptr, &mut result);
+            result
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `key`, which may be a `:`-separated path into nested dictionaries, is set.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Look up `key`, which may be a `:`-separated path into nested dictionaries.
+    pub fn get(&self, key: &str) -> Option<ValueRef<'_>> {
+        let key = cpp::String::new(key);
+        unsafe {
+            let mut ptr = std::ptr::null();
+            ffi::vt_Dictionary_GetValueAtPath(self.ptr, key.ptr, &mut ptr);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(ValueRef::from_ptr(ptr))
             }
-        } else {
-            None
         }
     }
 
-    fn is_holding(value: &Value) -> bool {
+    /// Set `key`, which may be a `:`-separated path into nested dictionaries, creating any
+    /// intermediate dictionaries as needed.
+    pub fn insert(&mut self, key: &str, value: &Value) {
+        let key = cpp::String::new(key);
         unsafe {
-            let mut result = false;
-            ffi::vt_Value_IsHolding_VtVec3fArray(value.ptr, &mut result);
-            result
+            ffi::vt_Dictionary_SetValueAtPath(self.ptr, key.ptr, value.ptr);
         }
     }
 
-    fn as_value(member: &Self) -> Value {
+    /// Remove `key`, which may be a `:`-separated path into nested dictionaries. Returns
+    /// whether it was set.
+    pub fn remove(&mut self, key: &str) -> bool {
+        if !self.contains_key(key) {
+            return false;
+        }
+
+        let key = cpp::String::new(key);
+        unsafe {
+            ffi::vt_Dictionary_EraseValueAtPath(self.ptr, key.ptr);
+        }
+        true
+    }
+
+    pub fn keys(&self) -> Vec<String> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_VtVec3fArray(member.ptr, &mut ptr);
-            Value { ptr }
+            ffi::vt_Dictionary_keys(self.ptr, &mut ptr);
+            cpp::StringVector::from_ptr(ptr).to_vec()
+        }
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary::new()
+    }
+}
+
+impl Clone for Dictionary {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::vt_Dictionary_copy(self.ptr, &mut ptr);
+            Dictionary { ptr }
+        }
+    }
+}
+
+impl fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for key in self.keys() {
+            if let Some(value) = self.get(&key) {
+                map.entry(&key, &*value);
+            }
+        }
+        map.finish()
+    }
+}
+
+impl Drop for Dictionary {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::vt_Dictionary_dtor(self.ptr);
+        }
+    }
+}
+
+/// A borrowed [`Dictionary`] held by another object, such as a [`Value`].
+#[repr(transparent)]
+pub struct DictionaryRef<'a> {
+    pub(crate) ptr: *const ffi::vt_Dictionary_t,
+    _marker: PhantomData<&'a Dictionary>,
+}
+
+impl<'a> DictionaryRef<'a> {
+    /// # Safety
+    /// `ptr` must point to a valid dictionary that outlives `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::vt_Dictionary_t) -> Self {
+        DictionaryRef {
+            ptr,
+            _marker: PhantomData,
         }
     }
 }
 
-impl ValueMember for DVec3 {
-    fn get(value: &Value) -> Option<&Self> {
+impl<'a> std::ops::Deref for DictionaryRef<'a> {
+    type Target = Dictionary;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const DictionaryRef as *const Dictionary) }
+    }
+}
+
+impl ValueMember for Dictionary {
+    type Ref<'a> = DictionaryRef<'a>;
+
+    fn get(value: &Value) -> Option<Self::Ref<'_>> {
         if Self::is_holding(value) {
             unsafe {
                 let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_GfVec3d(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut DVec3))
+                ffi::vt_Value_Get_VtDictionary(value.ptr, &mut ptr);
+                Some(DictionaryRef::from_ptr(ptr))
             }
         } else {
             None
@@ -948,7 +1703,7 @@ impl ValueMember for DVec3 {
     fn is_holding(value: &Value) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_GfVec3d(value.ptr, &mut result);
+            ffi::vt_Value_IsHolding_VtDictionary(value.ptr, &mut result);
             result
         }
     }
@@ -956,44 +1711,300 @@ impl ValueMember for DVec3 {
     fn as_value(member: &Self) -> Value {
         unsafe {
             let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_GfVec3d(
-                *(member as *const DVec3 as *const ffi::gf_Vec3d_t),
-                &mut ptr,
-            );
+            ffi::vt_Value_from_VtDictionary(member.ptr, &mut ptr);
             Value { ptr }
         }
     }
-}
 
-impl ValueMember for Vec4 {
-    fn get(value: &Value) -> Option<&Self> {
-        if Self::is_holding(value) {
-            unsafe {
-                let mut ptr = std::ptr::null();
-                ffi::vt_Value_Get_GfVec4f(value.ptr, &mut ptr);
-                Some(&*(ptr as *mut Vec4))
+    fn tf_type() -> tf::Type {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            ffi::tf_Type_Find_VtDictionary(&mut ptr);
+            tf::Type { ptr }
+        }
+    }
+
+    fn attribute_get(attr: &usd::Attribute, time: usd::TimeCode) -> Option<Self> {
+        unsafe {
+            let dictionary = Dictionary::new();
+            let mut result = false;
+            ffi::usd_Attribute_Get_VtDictionary(attr.ptr, dictionary.ptr, time.0, &mut result);
+            if result {
+                Some(dictionary)
+            } else {
+                None
             }
-        } else {
-            None
         }
     }
 
-    fn is_holding(value: &Value) -> bool {
+    fn attribute_set(attr: &usd::Attribute, member: &Self, time: usd::TimeCode) -> bool {
         unsafe {
             let mut result = false;
-            ffi::vt_Value_IsHolding_GfVec4f(value.ptr, &mut result);
+            ffi::usd_Attribute_Set_VtDictionary(attr.ptr, member.ptr, time.0, &mut result);
             result
         }
     }
+}
 
-    fn as_value(member: &Self) -> Value {
-        unsafe {
-            let mut ptr = std::ptr::null_mut();
-            ffi::vt_Value_from_GfVec4f(
-                *(member as *const Vec4 as *const ffi::gf_Vec4f_t),
-                &mut ptr,
-            );
-            Value { ptr }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_array_round_trip<T: ArrayElement + PartialEq + fmt::Debug>(elements: Vec<T>) {
+        let array = Array::from(elements);
+        let value = Value::from_member(&array);
+        assert!(value.is_holding::<Array<T>>());
+        assert!(value.is_array_valued());
+        assert_eq!(value.array_size(), array.len());
+        assert_eq!(*value.get::<Array<T>>().unwrap(), array);
+    }
+
+    #[test]
+    fn pod_round_trip() {
+        assert_eq!(Value::from_member(&true).get::<bool>(), Some(&true));
+        assert_eq!(Value::from_member(&7u8).get::<u8>(), Some(&7u8));
+        assert_eq!(Value::from_member(&7u32).get::<u32>(), Some(&7u32));
+        assert_eq!(Value::from_member(&-7i64).get::<i64>(), Some(&-7i64));
+        assert_eq!(
+            Value::from_member(&f16::from_f32(0.5)).get::<f16>(),
+            Some(&f16::from_f32(0.5))
+        );
+        assert_eq!(
+            Value::from_member(&sdf::TimeCode(24.0)).get::<sdf::TimeCode>(),
+            Some(&sdf::TimeCode(24.0))
+        );
+        assert_eq!(
+            Value::from_member(&DVec3::new(1.0, 2.0, 3.0)).get::<DVec3>(),
+            Some(&DVec3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(
+            Value::from_member(&IVec2::new(1, 2)).get::<IVec2>(),
+            Some(&IVec2::new(1, 2))
+        );
+
+        let m = DMat4::from_translation(DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(Value::from_member(&m).get::<DMat4>(), Some(&m));
+
+        let q = DQuat::from_rotation_y(1.0);
+        assert_eq!(Value::from_member(&q).get::<DQuat>(), Some(&q));
+    }
+
+    #[test]
+    fn pod_type_is_not_confused() {
+        let value = Value::from_member(&1i32);
+        assert!(value.is_holding::<i32>());
+        assert!(!value.is_holding::<u32>());
+        assert!(!value.is_holding::<i64>());
+        assert!(value.get::<f32>().is_none());
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let value = Value::from_member(&"hello".to_string());
+        assert_eq!(value.as_string(), Some("hello"));
+        assert!(value.get::<i32>().is_none());
+    }
+
+    #[test]
+    fn array_round_trip() {
+        check_array_round_trip(vec![1i64, 2, 3]);
+        check_array_round_trip(vec![1u8, 2, 3]);
+        check_array_round_trip(vec![DVec3::ZERO, DVec3::ONE]);
+        check_array_round_trip(vec![DMat4::IDENTITY]);
+        check_array_round_trip(vec![Quat::IDENTITY]);
+        check_array_round_trip(vec![sdf::TimeCode(1.0), sdf::TimeCode(2.0)]);
+    }
+
+    #[test]
+    fn array_ref_views_value() {
+        let value = Value::from_member(&IntArray::from(vec![1, 2, 3]));
+        let array = value.as_int_array().unwrap();
+        assert_eq!(array.as_slice(), &[1, 2, 3]);
+        assert!(value.as_float_array().is_none());
+    }
+
+    #[test]
+    fn array_slices() {
+        let mut array = FloatArray::from(vec![1.0, 2.0, 3.0]);
+        assert_eq!(array.as_slice(), &[1.0, 2.0, 3.0]);
+
+        array.as_mut_slice()[1] = 5.0;
+        assert_eq!(array.as_slice(), &[1.0, 5.0, 3.0]);
+
+        let empty = FloatArray::new();
+        assert!(empty.as_slice().is_empty());
+    }
+
+    #[test]
+    fn array_mut_slice_detaches_shared_storage() {
+        let mut a = IntArray::from(vec![1, 2, 3]);
+        let b = a.clone();
+        a.as_mut_slice()[0] = 10;
+        assert_eq!(a.as_slice(), &[10, 2, 3]);
+        assert_eq!(b.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn array_from_iterator_and_extend() {
+        let mut array: Vec3Array = (0..3).map(|i| Vec3::splat(i as f32)).collect();
+        assert_eq!(array.len(), 3);
+        assert_eq!(array[2], Vec3::splat(2.0));
+
+        array.extend([Vec3::ONE]);
+        array.push(Vec3::ZERO);
+        assert_eq!(array.len(), 5);
+        assert_eq!(array.iter().copied().sum::<Vec3>(), Vec3::splat(4.0));
+
+        for v in &mut array {
+            *v *= 2.0;
+        }
+        assert_eq!(array[1], Vec3::splat(2.0));
+
+        array.clear();
+        assert!(array.is_empty());
+    }
+
+    #[test]
+    fn array_api() {
+        let mut array = DoubleArray::with_capacity(4);
+        assert!(array.is_empty());
+        assert!(array.capacity() >= 4);
+
+        array.push(1.0);
+        array.push(2.0);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[1], 2.0);
+
+        array[0] = 3.0;
+        assert_eq!(*array.at(0), 3.0);
+
+        array.resize(4);
+        assert_eq!(array.as_slice(), &[3.0, 2.0, 0.0, 0.0]);
+
+        array.reserve(16);
+        assert!(array.capacity() >= 16);
+    }
+
+    #[test]
+    fn token_array_refs_borrow_the_array() {
+        let tokens = TokenArray::new(&["a", "b"]);
+        let texts: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(texts, ["a", "b"]);
+
+        let value = Value::from_member(&tokens);
+        let held = value.get::<TokenArray>().unwrap();
+        assert_eq!(held.size(), 2);
+        assert_eq!(held.at(1).text(), "b");
+    }
+
+    #[test]
+    fn value_introspection() {
+        let empty = Value::new();
+        assert!(empty.is_empty());
+        assert!(!empty.is_array_valued());
+        assert_eq!(empty.array_size(), 0);
+
+        let scalar = Value::from_member(&1.5f32);
+        assert!(!scalar.is_empty());
+        assert_eq!(scalar.type_name(), "float");
+        assert!(!scalar.is_array_valued());
+        assert_eq!(scalar.array_size(), 0);
+
+        let array = Value::from_member(&Vec3Array::from(vec![Vec3::ZERO; 3]));
+        assert_eq!(array.type_name(), "VtArray<GfVec3f>");
+        assert!(array.is_array_valued());
+        assert_eq!(array.array_size(), 3);
+    }
+
+    #[test]
+    fn value_clone_and_eq() {
+        let a = Value::from_member(&2i32);
+        let b = a.clone();
+        assert_eq!(a, b);
+        assert_ne!(a, Value::from_member(&3i32));
+        assert_ne!(a, Value::from_member(&2.0f64));
+        assert_eq!(Value::new(), Value::default());
+    }
+
+    #[test]
+    fn value_display() {
+        assert_eq!(Value::from_member(&42i32).to_string(), "42");
+        assert_eq!(Value::from_member(&"text".to_string()).to_string(), "text");
+        assert_eq!(
+            Value::from_member(&IntArray::from(vec![1, 2])).to_string(),
+            "[1, 2]"
+        );
+    }
+
+    #[test]
+    fn value_kind() {
+        assert!(matches!(Value::new().kind(), ValueKind::Empty));
+        assert!(matches!(Value::from_member(&3i32).kind(), ValueKind::Int(3)));
+        assert!(matches!(Value::from_member(&7u32).kind(), ValueKind::UInt(7)));
+        assert!(matches!(
+            Value::from_member(&DVec3::ONE).kind(),
+            ValueKind::Double3(v) if v == DVec3::ONE
+        ));
+
+        let string = Value::from_member(&"s".to_string());
+        assert!(matches!(string.kind(), ValueKind::String("s")));
+
+        let token = Value::from_member(&tf::Token::new("tok"));
+        match token.kind() {
+            ValueKind::Token(t) => assert_eq!(t.text(), "tok"),
+            _ => panic!("expected a token"),
         }
+
+        let asset = Value::from_member(&sdf::AssetPath::new("a.usda"));
+        match asset.kind() {
+            ValueKind::AssetPath(a) => assert_eq!(a.asset_path(), "a.usda"),
+            _ => panic!("expected an asset path"),
+        }
+
+        let array = Value::from_member(&FloatArray::from(vec![1.0, 2.0]));
+        match array.kind() {
+            ValueKind::FloatArray(a) => assert_eq!(a.as_slice(), &[1.0, 2.0]),
+            _ => panic!("expected a float array"),
+        }
+    }
+
+    #[test]
+    fn value_kind_dictionary() {
+        let mut dict = Dictionary::new();
+        dict.insert("outer:inner", &Value::from_member(&1.0f64));
+        let value = Value::from_member(&dict);
+        match value.kind() {
+            ValueKind::Dictionary(d) => {
+                let inner = d.get("outer:inner").unwrap();
+                assert_eq!(inner.get::<f64>(), Some(&1.0));
+            }
+            _ => panic!("expected a dictionary"),
+        }
+    }
+
+    #[test]
+    fn array_tf_type() {
+        assert_eq!(Vec3Array::tf_type().type_name(), "VtArray<GfVec3f>");
+        assert_ne!(Vec3Array::tf_type(), Vec3::tf_type());
+    }
+
+    #[test]
+    fn dictionary_key_paths() {
+        let mut dict = Dictionary::new();
+        dict.insert("a:b", &Value::from_member(&1i32));
+        dict.insert("c", &Value::from_member(&2i32));
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.keys(), ["a", "c"]);
+        assert!(dict.contains_key("a:b"));
+        assert!(dict.contains_key("a"));
+        assert!(!dict.contains_key("a:c"));
+
+        assert!(dict.remove("a:b"));
+        assert!(!dict.contains_key("a:b"));
+        assert!(!dict.contains_key("a"));
+        assert!(!dict.remove("a:b"));
+
+        assert!(dict.remove("c"));
+        assert!(dict.is_empty());
     }
 }